@@ -0,0 +1,411 @@
+use crate::cargo_metadata::{MissingCrates, Package};
+use anyhow::{Context, bail};
+use flate2::read::ZlibDecoder;
+use serde::Deserialize;
+use std::io::Read;
+use std::path::Path;
+
+const SECTION_NAME: &[u8] = b".dep-v0";
+
+const MAX_DEPENDENCY_LIST_SIZE: u64 = 8 * 1024 * 1024;
+
+#[derive(Deserialize)]
+struct VersionInfo {
+    packages: Vec<AuditedPackage>,
+}
+
+#[derive(Deserialize)]
+struct AuditedPackage {
+    name: String,
+    version: String,
+    #[serde(default)]
+    kind: AuditedKind,
+    #[serde(default)]
+    root: bool,
+}
+
+#[derive(Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum AuditedKind {
+    #[default]
+    Runtime,
+    Build,
+}
+
+#[cfg_attr(test, derive(Debug))]
+#[derive(PartialEq, Eq)]
+pub struct BinaryCrate {
+    pub name: String,
+    pub version: String,
+}
+
+pub fn try_get_binary_crates(path: &Path, build: bool) -> anyhow::Result<Vec<BinaryCrate>> {
+    // binaries are far larger than license files, so are not read through FileIO
+    let binary =
+        std::fs::read(path).with_context(|| format!("failed to read binary {}", path.display()))?;
+    binary_crates(&binary, build)
+        .with_context(|| format!("failed to read the dependencies of {}", path.display()))
+}
+
+fn binary_crates(binary: &[u8], build: bool) -> anyhow::Result<Vec<BinaryCrate>> {
+    let mut json = String::new();
+    ZlibDecoder::new(elf_section(binary, SECTION_NAME)?)
+        .take(MAX_DEPENDENCY_LIST_SIZE)
+        .read_to_string(&mut json)
+        .context("failed to decompress the dependency list")?;
+    if json.len() as u64 >= MAX_DEPENDENCY_LIST_SIZE {
+        bail!("the dependency list is too large, the limit is {MAX_DEPENDENCY_LIST_SIZE} bytes");
+    }
+    let version_info: VersionInfo =
+        serde_json::from_str(&json).context("failed to parse the dependency list")?;
+    Ok(version_info
+        .packages
+        .into_iter()
+        .filter(|package| !package.root && (build || package.kind == AuditedKind::Runtime))
+        .map(|package| BinaryCrate {
+            name: package.name,
+            version: package.version,
+        })
+        .collect())
+}
+
+fn elf_section<'a>(binary: &'a [u8], name: &[u8]) -> anyhow::Result<&'a [u8]> {
+    let elf = Elf::parse(binary)?;
+    let names = elf.section(elf.section_names_index)?;
+    for index in 0..elf.section_count {
+        let name_offset = usize::try_from(elf.encoding.u32(elf.section_header(index)?)?)?;
+        if names
+            .get(name_offset..)
+            .and_then(|names| names.split(|&byte| byte == 0).next())
+            == Some(name)
+        {
+            return elf.section(index);
+        }
+    }
+    bail!(
+        "no {} section, the binary was not built with cargo-auditable",
+        String::from_utf8_lossy(name)
+    )
+}
+
+#[derive(Clone, Copy)]
+struct Encoding {
+    is_64_bit: bool,
+    is_little_endian: bool,
+}
+
+impl Encoding {
+    fn word(self, bytes: &[u8]) -> anyhow::Result<usize> {
+        if self.is_64_bit {
+            let bytes: [u8; 8] = bytes
+                .get(..8)
+                .context("ELF binary is truncated")?
+                .try_into()?;
+            Ok(usize::try_from(if self.is_little_endian {
+                u64::from_le_bytes(bytes)
+            } else {
+                u64::from_be_bytes(bytes)
+            })?)
+        } else {
+            Ok(usize::try_from(self.u32(bytes)?)?)
+        }
+    }
+
+    fn u32(self, bytes: &[u8]) -> anyhow::Result<u32> {
+        let bytes: [u8; 4] = bytes
+            .get(..4)
+            .context("ELF binary is truncated")?
+            .try_into()?;
+        Ok(if self.is_little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn u16(self, bytes: &[u8]) -> anyhow::Result<usize> {
+        let bytes: [u8; 2] = bytes
+            .get(..2)
+            .context("ELF binary is truncated")?
+            .try_into()?;
+        Ok(usize::from(if self.is_little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        }))
+    }
+}
+
+struct Elf<'a> {
+    binary: &'a [u8],
+    encoding: Encoding,
+    section_headers: usize,
+    section_header_size: usize,
+    section_count: usize,
+    section_names_index: usize,
+}
+
+impl<'a> Elf<'a> {
+    fn parse(binary: &'a [u8]) -> anyhow::Result<Self> {
+        if !binary.starts_with(b"\x7fELF") || binary.len() < 64 {
+            bail!("not an ELF binary");
+        }
+        let encoding = Encoding {
+            is_64_bit: binary[4] == 2,
+            is_little_endian: binary[5] == 1,
+        };
+        // the section header fields follow the entry point and program header addresses
+        let (section_headers, sizes) = if encoding.is_64_bit {
+            (&binary[0x28..], &binary[0x3A..])
+        } else {
+            (&binary[0x20..], &binary[0x2E..])
+        };
+        let section_header_size = encoding.u16(sizes)?;
+        if section_header_size < 0x28 {
+            bail!("invalid ELF section header size {section_header_size}");
+        }
+        Ok(Self {
+            binary,
+            encoding,
+            section_headers: encoding.word(section_headers)?,
+            section_header_size,
+            section_count: encoding.u16(&sizes[2..])?,
+            section_names_index: encoding.u16(&sizes[4..])?,
+        })
+    }
+
+    fn section_header(&self, index: usize) -> anyhow::Result<&'a [u8]> {
+        index
+            .checked_mul(self.section_header_size)
+            .and_then(|offset| offset.checked_add(self.section_headers))
+            .and_then(|start| Some(start..start.checked_add(self.section_header_size)?))
+            .and_then(|range| self.binary.get(range))
+            .context("section header is out of bounds")
+    }
+
+    fn section(&self, index: usize) -> anyhow::Result<&'a [u8]> {
+        let header = self.section_header(index)?;
+        let (offset, size) = if self.encoding.is_64_bit {
+            (&header[0x18..], &header[0x20..])
+        } else {
+            (&header[0x10..], &header[0x14..])
+        };
+        let start = self.encoding.word(offset)?;
+        start
+            .checked_add(self.encoding.word(size)?)
+            .and_then(|end| self.binary.get(start..end))
+            .context("section is out of bounds")
+    }
+}
+
+pub fn packages_in_binary(
+    metadata: cargo_metadata::Metadata,
+    binary_crates: &[BinaryCrate],
+    ignored_crates: &[String],
+) -> anyhow::Result<(Vec<Package>, MissingCrates)> {
+    let binary_crates: Vec<&BinaryCrate> = binary_crates
+        .iter()
+        .filter(|binary_crate| !ignored_crates.contains(&binary_crate.name.replace('-', "_")))
+        .collect();
+    let mut found = Vec::new();
+    let mut packages = Vec::new();
+    for package in metadata.packages {
+        let binary_crate = BinaryCrate {
+            name: package.name.to_string(),
+            version: package.version.to_string(),
+        };
+        if binary_crates.contains(&&binary_crate) && !found.contains(&binary_crate) {
+            packages.push(Package::try_from_metadata(package)?);
+            found.push(binary_crate);
+        }
+    }
+    let missing = binary_crates
+        .into_iter()
+        .filter(|binary_crate| !found.contains(binary_crate))
+        .map(|binary_crate| format!("{} {}", binary_crate.name, binary_crate.version))
+        .collect();
+    Ok((packages, MissingCrates(missing)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+    use std::io::Write;
+
+    const DEPENDENCIES: &str = r#"{"packages": [
+        {"name": "example", "version": "0.1.0", "source": "local", "root": true, "dependencies": [1, 2]},
+        {"name": "runtime-dep", "version": "1.0.0", "source": "crates.io"},
+        {"name": "build-dep", "version": "2.0.0", "source": "crates.io", "kind": "build"}
+    ]}"#;
+
+    fn put(binary: &mut [u8], at: usize, value: usize, width: usize, is_little_endian: bool) {
+        let value = value as u64;
+        if is_little_endian {
+            binary[at..at + width].copy_from_slice(&value.to_le_bytes()[..width]);
+        } else {
+            binary[at..at + width].copy_from_slice(&value.to_be_bytes()[8 - width..]);
+        }
+    }
+
+    // a minimal ELF binary of only section headers, the section names being the last section
+    fn elf(is_64_bit: bool, is_little_endian: bool, sections: &[(&str, &[u8])]) -> Vec<u8> {
+        let (word, header_size) = if is_64_bit { (8, 64) } else { (4, 40) };
+        let mut binary = vec![0; 64];
+        binary[..4].copy_from_slice(b"\x7fELF");
+        binary[4] = if is_64_bit { 2 } else { 1 };
+        binary[5] = if is_little_endian { 1 } else { 2 };
+        let mut names = b"\0.shstrtab\0".to_vec();
+        let mut headers = vec![(0, 0, 0)];
+        for (name, data) in sections {
+            headers.push((names.len(), binary.len(), data.len()));
+            names.extend(name.as_bytes());
+            names.push(0);
+            binary.extend(*data);
+        }
+        headers.insert(1, (1, binary.len(), names.len()));
+        binary.extend(&names);
+
+        let section_headers = binary.len();
+        let (offset_at, size_at) = if is_64_bit {
+            (0x18, 0x20)
+        } else {
+            (0x10, 0x14)
+        };
+        for (name, offset, size) in &headers {
+            let start = binary.len();
+            binary.resize(start + header_size, 0);
+            put(&mut binary, start, *name, 4, is_little_endian);
+            put(
+                &mut binary,
+                start + offset_at,
+                *offset,
+                word,
+                is_little_endian,
+            );
+            put(&mut binary, start + size_at, *size, word, is_little_endian);
+        }
+        let (headers_at, counts_at) = if is_64_bit {
+            (0x28, 0x3A)
+        } else {
+            (0x20, 0x2E)
+        };
+        put(
+            &mut binary,
+            headers_at,
+            section_headers,
+            word,
+            is_little_endian,
+        );
+        put(&mut binary, counts_at, header_size, 2, is_little_endian);
+        put(
+            &mut binary,
+            counts_at + 2,
+            headers.len(),
+            2,
+            is_little_endian,
+        );
+        put(&mut binary, counts_at + 4, 1, 2, is_little_endian);
+        binary
+    }
+
+    fn compressed(text: &str) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn binary_crate(name: &str, version: &str) -> BinaryCrate {
+        BinaryCrate {
+            name: name.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn dependencies_are_read_from_the_section_of_either_elf_class_and_byte_order() {
+        let dependencies = compressed(DEPENDENCIES);
+        for (is_64_bit, is_little_endian) in [(true, true), (false, false)] {
+            let binary = elf(
+                is_64_bit,
+                is_little_endian,
+                &[(".text", b"code"), (".dep-v0", &dependencies)],
+            );
+
+            assert_eq!(
+                vec![binary_crate("runtime-dep", "1.0.0")],
+                binary_crates(&binary, false).unwrap()
+            );
+            assert_eq!(
+                vec![
+                    binary_crate("runtime-dep", "1.0.0"),
+                    binary_crate("build-dep", "2.0.0")
+                ],
+                binary_crates(&binary, true).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn binaries_without_the_section_are_an_error() {
+        assert_eq!(
+            "no .dep-v0 section, the binary was not built with cargo-auditable",
+            binary_crates(&elf(true, true, &[(".text", b"code")]), false)
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(binary_crates(b"MZ not an ELF binary", false).is_err());
+    }
+
+    #[test]
+    fn dependency_lists_over_the_limit_are_an_error() {
+        let dependencies =
+            compressed(&" ".repeat(usize::try_from(MAX_DEPENDENCY_LIST_SIZE).unwrap()));
+        let binary = elf(true, true, &[(".dep-v0", &dependencies)]);
+
+        assert!(
+            binary_crates(&binary, false)
+                .unwrap_err()
+                .to_string()
+                .starts_with("the dependency list is too large")
+        );
+    }
+
+    #[test]
+    fn binary_crates_are_matched_to_packages_by_name_and_version() {
+        let package = |name: &str, version: &str| {
+            format!(
+                r#"{{"name": "{name}", "version": "{version}", "id": "{name} {version}",
+                "source": null, "dependencies": [], "features": {{}}, "targets": [],
+                "manifest_path": "{name}-{version}/Cargo.toml"}}"#
+            )
+        };
+        let metadata = serde_json::from_str(&format!(
+            r#"{{"packages": [{}, {}], "workspace_members": [], "resolve": null,
+            "workspace_root": "", "target_directory": "target", "version": 1}}"#,
+            package("runtime-dep", "1.0.0"),
+            package("runtime-dep", "0.9.0")
+        ))
+        .unwrap();
+
+        let (packages, missing) = packages_in_binary(
+            metadata,
+            &[
+                binary_crate("runtime-dep", "1.0.0"),
+                binary_crate("absent", "1.0.0"),
+            ],
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec!["runtime-dep-1.0.0".to_string()],
+            packages
+                .iter()
+                .map(|package| package.path.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(MissingCrates(vec!["absent 1.0.0".to_string()]), missing);
+    }
+}
@@ -1,8 +1,13 @@
+use crate::log::warning;
 use anyhow::Context;
 use cargo_metadata::camino::Utf8PathBuf;
+use colored::Colorize;
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::io::Read;
+use std::path::Path;
 
 #[derive(Debug, Eq, Clone)]
 pub struct Package {
@@ -10,6 +15,8 @@ pub struct Package {
     pub path: Utf8PathBuf,
     pub url: Option<String>,
     pub license: Option<String>,
+    pub authors: Vec<String>,
+    pub source: Option<String>,
 }
 
 #[cfg(test)]
@@ -20,12 +27,20 @@ impl Package {
             normalised_name: name.to_string(),
             url: None,
             license: None,
+            authors: Vec::new(),
+            source: None,
         }
     }
 }
 
 impl Package {
-    fn try_from_metadata(package: cargo_metadata::Package) -> anyhow::Result<Self> {
+    pub fn is_git_or_path(&self) -> bool {
+        self.source
+            .as_deref()
+            .is_none_or(|source| source.starts_with("git+"))
+    }
+
+    pub fn try_from_metadata(package: cargo_metadata::Package) -> anyhow::Result<Self> {
         Ok(Self {
             normalised_name: package.name.to_string().replace('-', "_"),
             path: package
@@ -35,6 +50,8 @@ impl Package {
                 .to_path_buf(),
             url: package.repository,
             license: package.license,
+            authors: package.authors,
+            source: package.source.map(|source| source.repr),
         })
     }
 }
@@ -66,6 +83,29 @@ impl Hash for Package {
     }
 }
 
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct MissingCrates(pub Vec<String>);
+
+impl Display for MissingCrates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        writeln!(
+            f,
+            "{}",
+            warning(&format!(
+                "{} - did not find the sources of crates for:",
+                "missing".bold()
+            ))
+        )?;
+        for name in &self.0 {
+            writeln!(f, "\t{}", name.bold())?;
+        }
+        Ok(())
+    }
+}
+
 pub fn filtered_packages(
     all_packages: Vec<Package>,
     crates_we_want: &BTreeSet<String>,
@@ -77,15 +117,37 @@ pub fn filtered_packages(
 }
 
 pub fn try_get_packages() -> anyhow::Result<Vec<Package>> {
+    packages_from_metadata(try_get_metadata()?)
+}
+
+pub fn try_get_metadata() -> anyhow::Result<cargo_metadata::Metadata> {
     cargo_metadata::MetadataCommand::new()
         .exec()
-        .context("failed to call cargo metadata")?
+        .context("failed to call cargo metadata")
+}
+
+pub fn packages_from_metadata(metadata: cargo_metadata::Metadata) -> anyhow::Result<Vec<Package>> {
+    metadata
         .packages
         .into_iter()
         .map(Package::try_from_metadata)
         .collect()
 }
 
+pub fn read_metadata(path: &Path) -> anyhow::Result<cargo_metadata::Metadata> {
+    let contents = if path == Path::new("-") {
+        let mut contents = String::new();
+        std::io::stdin()
+            .read_to_string(&mut contents)
+            .context("failed to read cargo metadata from stdin")?;
+        contents
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read cargo metadata {}", path.display()))?
+    };
+    serde_json::from_str(&contents).context("failed to parse cargo metadata")
+}
+
 #[cfg(test)]
 mod tests {
     use super::Package;
@@ -178,12 +240,16 @@ mod tests {
                 path: Utf8PathBuf::from("/some/path/1"),
                 url: Some("https://github.com/toml-rs/toml".to_string()),
                 license: Some("MIT".to_string()),
+                authors: Vec::new(),
+                source: None,
             },
             Package {
                 normalised_name: "toml".to_string(),
                 path: Utf8PathBuf::from("/some/path/2"),
                 url: Some("https://github.com/toml-rs/toml".to_string()),
                 license: Some("MIT".to_string()),
+                authors: Vec::new(),
+                source: None,
             }
         );
     }
@@ -196,12 +262,16 @@ mod tests {
                 path: Utf8PathBuf::from("/some/path/1"),
                 url: Some("https://github.com/toml-rs/toml".to_string()),
                 license: Some("MIT".to_string()),
+                authors: Vec::new(),
+                source: None,
             },
             Package {
                 normalised_name: "toml".to_string(),
                 path: Utf8PathBuf::from("/some/path/2"),
                 url: Some("https://github.com/toml-rs/toml".to_string()),
                 license: Some("Apache-2.0".to_string()),
+                authors: Vec::new(),
+                source: None,
             }
         );
     }
@@ -213,18 +283,24 @@ mod tests {
             path: Utf8PathBuf::from("/some/path/1"),
             url: None,
             license: Some("MIT".to_string()),
+            authors: Vec::new(),
+            source: None,
         };
         let package_2 = Package {
             normalised_name: "toml".to_string(),
             path: Utf8PathBuf::from("/some/path/2"),
             url: None,
             license: Some("MIT".to_string()),
+            authors: Vec::new(),
+            source: None,
         };
         let package_3 = Package {
             normalised_name: "toml".to_string(),
             path: Utf8PathBuf::from("/some/path/3"),
             url: None,
             license: Some("Apache-2.0".to_string()),
+            authors: Vec::new(),
+            source: None,
         };
 
         let mut set = HashSet::new();
@@ -305,12 +381,16 @@ mod tests {
             path: Utf8PathBuf::new(),
             url: None,
             license: Some("MIT".to_string()),
+            authors: Vec::new(),
+            source: None,
         };
         let b = Package {
             normalised_name: "beta".to_string(),
             path: Utf8PathBuf::new(),
             url: None,
             license: Some("MIT".to_string()),
+            authors: Vec::new(),
+            source: None,
         };
         assert!(a < b);
     }
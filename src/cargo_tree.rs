@@ -1,7 +1,8 @@
 use crate::config::Config;
 use anyhow::Context;
+use cargo_metadata::{DependencyKind, Metadata, NodeDep, PackageId, TargetKind};
 use itertools::Itertools;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::process::Command;
 
 pub fn crate_names(config: &Config) -> anyhow::Result<BTreeSet<String>> {
@@ -22,6 +23,110 @@ fn to_crate_names(output: Vec<u8>, ignored_crates: &[String]) -> anyhow::Result<
         .collect())
 }
 
+pub fn crate_names_from_metadata(
+    metadata: &Metadata,
+    config: &Config,
+) -> anyhow::Result<BTreeSet<String>> {
+    let resolve = metadata.resolve.as_ref().context(
+        "cargo metadata has no dependency graph, it must be generated without --no-deps",
+    )?;
+    let packages: HashMap<&PackageId, &cargo_metadata::Package> = metadata
+        .packages
+        .iter()
+        .map(|package| (&package.id, package))
+        .collect();
+    let dependencies: HashMap<&PackageId, &[NodeDep]> = resolve
+        .nodes
+        .iter()
+        .map(|node| (&node.id, node.deps.as_slice()))
+        .collect();
+
+    let mut depths: HashMap<&PackageId, u8> = roots(metadata, &packages, config)
+        .into_iter()
+        .map(|root| (root, 0))
+        .collect();
+    let mut queue: VecDeque<&PackageId> = depths.keys().copied().collect();
+    while let Some(id) = queue.pop_front() {
+        let depth = depths[id];
+        if config
+            .global
+            .depth
+            .is_some_and(|max_depth| depth >= max_depth)
+        {
+            continue;
+        }
+        for dependency in dependencies.get(id).copied().unwrap_or_default() {
+            if is_followed(dependency, config) && !depths.contains_key(&dependency.pkg) {
+                depths.insert(&dependency.pkg, depth.saturating_add(1));
+                queue.push_back(&dependency.pkg);
+            }
+        }
+    }
+
+    Ok(depths
+        .keys()
+        .filter_map(|id| packages.get(id))
+        .filter_map(|package| library_name(package))
+        .filter(|crate_name| !config.global.ignore.contains(crate_name))
+        .collect())
+}
+
+fn roots<'a>(
+    metadata: &'a Metadata,
+    packages: &HashMap<&PackageId, &cargo_metadata::Package>,
+    config: &Config,
+) -> Vec<&'a PackageId> {
+    if !config.global.exclude.is_empty() {
+        return metadata
+            .workspace_members
+            .iter()
+            .filter(|id| {
+                packages.get(id).is_some_and(|package| {
+                    !config.global.exclude.contains(&package.name.to_string())
+                })
+            })
+            .collect();
+    }
+    if metadata.workspace_default_members.is_available() {
+        metadata.workspace_default_members.iter().collect()
+    } else {
+        metadata.workspace_members.iter().collect()
+    }
+}
+
+// metadata from before cargo 1.41 has no dependency kinds, which are then all normal
+fn is_followed(dependency: &NodeDep, config: &Config) -> bool {
+    dependency.dep_kinds.is_empty()
+        || dependency
+            .dep_kinds
+            .iter()
+            .any(|dep_kind| match dep_kind.kind {
+                DependencyKind::Development => config.global.dev,
+                DependencyKind::Build => config.global.build,
+                _ => true,
+            })
+}
+
+fn library_name(package: &cargo_metadata::Package) -> Option<String> {
+    package
+        .targets
+        .iter()
+        .find(|target| {
+            target.kind.iter().any(|kind| {
+                matches!(
+                    kind,
+                    TargetKind::Lib
+                        | TargetKind::RLib
+                        | TargetKind::DyLib
+                        | TargetKind::CDyLib
+                        | TargetKind::StaticLib
+                        | TargetKind::ProcMacro
+                )
+            })
+        })
+        .map(|target| target.name.replace('-', "_"))
+}
+
 fn cargo_output_with_args(args: &[String]) -> anyhow::Result<Vec<u8>> {
     let output = Command::new("cargo")
         .args(args)
@@ -90,8 +195,9 @@ fn args(config: &Config) -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use crate::cargo_tree::{args, to_crate_names};
+    use crate::cargo_tree::{args, crate_names_from_metadata, to_crate_names};
     use crate::config::Config;
+    use cargo_metadata::Metadata;
     use std::collections::BTreeSet;
 
     #[test]
@@ -404,4 +510,76 @@ mod tests {
             args(&config)
         );
     }
+
+    fn package_json(name: &str, kind: &str) -> String {
+        format!(
+            r#"{{"name": "{name}", "version": "1.0.0", "id": "{name}", "source": null,
+            "dependencies": [], "features": {{}}, "manifest_path": "{name}/Cargo.toml",
+            "targets": [{{"name": "{name}", "kind": ["{kind}"], "crate_types": ["{kind}"],
+            "src_path": "{name}/src/lib.rs"}}]}}"#
+        )
+    }
+
+    fn dependency_json(name: &str, kind: &str) -> String {
+        format!(
+            r#"{{"name": "{name}", "pkg": "{name}", "dep_kinds": [{{"kind": {kind}, "target": null}}]}}"#
+        )
+    }
+
+    // root -> normal-dep -> nested, root -> dev-dep, root -> build-dep, root is binary only
+    fn metadata() -> Metadata {
+        let packages = [
+            package_json("root", "bin"),
+            package_json("normal-dep", "lib"),
+            package_json("nested", "proc-macro"),
+            package_json("dev-dep", "lib"),
+            package_json("build-dep", "lib"),
+        ]
+        .join(",");
+        let root_deps = [
+            dependency_json("normal-dep", "null"),
+            dependency_json("dev-dep", r#""dev""#),
+            dependency_json("build-dep", r#""build""#),
+        ]
+        .join(",");
+        let nested_dep = dependency_json("nested", "null");
+        serde_json::from_str(&format!(
+            r#"{{"packages": [{packages}], "workspace_members": ["root"],
+            "workspace_default_members": ["root"], "resolve": {{"root": "root", "nodes": [
+                {{"id": "root", "dependencies": [], "deps": [{root_deps}]}},
+                {{"id": "normal-dep", "dependencies": [], "deps": [{nested_dep}]}}
+            ]}}, "workspace_root": "", "target_directory": "target", "version": 1}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn crate_names_from_metadata_follow_normal_dependencies() {
+        assert_eq!(
+            BTreeSet::from(["nested".to_string(), "normal_dep".to_string()]),
+            crate_names_from_metadata(&metadata(), &Config::default()).unwrap()
+        );
+    }
+
+    #[test]
+    fn crate_names_from_metadata_apply_edges_depth_and_ignored_crates() {
+        let mut config = Config::default();
+        config.global.dev = true;
+        config.global.build = true;
+        config.global.depth = Some(1);
+        config.global.ignore = vec!["build_dep".to_string()];
+
+        assert_eq!(
+            BTreeSet::from(["dev_dep".to_string(), "normal_dep".to_string()]),
+            crate_names_from_metadata(&metadata(), &config).unwrap()
+        );
+    }
+
+    #[test]
+    fn metadata_without_dependency_graph_is_an_error() {
+        let mut metadata = metadata();
+        metadata.resolve = None;
+
+        assert!(crate_names_from_metadata(&metadata, &Config::default()).is_err());
+    }
 }
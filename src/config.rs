@@ -1,7 +1,8 @@
 use crate::GlobalArgs;
 use crate::file_io::FileIO;
 use crate::licenses::status::LicenseStatus;
-use anyhow::Context;
+use crate::licenses::validate::ConfidenceThreshold;
+use anyhow::{Context, bail};
 use serde::Deserialize;
 use std::collections::HashMap;
 
@@ -19,6 +20,9 @@ pub struct CrateConfig {
     pub skip: Vec<String>,
     pub allow: Option<LicenseStatus>,
     pub include: Vec<IncludedLicense>,
+    pub prefer: Vec<String>,
+    #[serde(rename = "confidence-threshold")]
+    pub confidence_threshold: Option<ConfidenceThreshold>,
 }
 
 #[derive(Debug, PartialEq, Eq, Deserialize, Clone)]
@@ -27,18 +31,74 @@ pub enum IncludedLicense {
     Text { name: String, text: String },
 }
 
+impl Config {
+    pub fn confidence_threshold(&self) -> ConfidenceThreshold {
+        self.global.confidence_threshold.unwrap_or_default()
+    }
+}
+
 impl GlobalArgs {
     fn merge(&mut self, other: Self) {
         self.dev |= other.dev;
         self.build |= other.build;
         self.all_features |= other.all_features;
         self.no_default_features |= other.no_default_features;
+        self.scan_content |= other.scan_content;
+        self.scan_license_dirs |= other.scan_license_dirs;
+        self.scan_headers |= other.scan_headers;
+        self.scan_embedded |= other.scan_embedded;
+        self.collect_embedded |= other.collect_embedded;
+        self.sibling_versions |= other.sibling_versions;
         if other.depth.is_some() {
             self.depth = other.depth;
         }
+        if other.license_texts.is_some() {
+            self.license_texts = other.license_texts;
+        }
+        if other.vendor.is_some() {
+            self.vendor = other.vendor;
+        }
+        if other.metadata.is_some() {
+            self.metadata = other.metadata;
+        }
+        if other.binary.is_some() {
+            self.binary = other.binary;
+        }
+        if other.confidence_threshold.is_some() {
+            self.confidence_threshold = other.confidence_threshold;
+        }
         self.feature.extend(other.feature);
         self.exclude.extend(other.exclude);
         self.ignore.extend(other.ignore);
+        if !other.prefer.is_empty() {
+            self.prefer = other.prefer;
+        }
+    }
+
+    // clap misses conflicts between the config and the command line, or across subcommand levels
+    fn validate_sources(&self) -> anyhow::Result<()> {
+        if self.vendor.is_some() {
+            if self.metadata.is_some() {
+                bail!("vendor cannot be used with metadata, the crates are read from Cargo.lock");
+            }
+            if self.binary.is_some() {
+                bail!("vendor cannot be used with binary, the crates are read from Cargo.lock");
+            }
+            if self.dev || self.build || self.depth.is_some() {
+                bail!(
+                    "vendor cannot be used with dev, build or depth, Cargo.lock does not record them"
+                );
+            }
+            if self.all_features || self.no_default_features || !self.feature.is_empty() {
+                bail!("vendor cannot be used with features, Cargo.lock does not record them");
+            }
+        }
+        if self.metadata.is_some()
+            && (self.all_features || self.no_default_features || !self.feature.is_empty())
+        {
+            bail!("metadata cannot be used with features, they are resolved by cargo metadata");
+        }
+        Ok(())
     }
 }
 
@@ -47,8 +107,10 @@ pub fn load_config(file_io: &impl FileIO, mut global_args: GlobalArgs) -> anyhow
         let mut config = parse_config(&file_io.read_file(&path)?)?;
         config.crate_configs = normalised_crate_names(config.crate_configs);
         config.global.merge(global_args);
+        config.global.validate_sources()?;
         Ok(config)
     } else {
+        global_args.validate_sources()?;
         Ok(Config {
             global: global_args,
             crate_configs: HashMap::new(),
@@ -73,6 +135,7 @@ mod tests {
     use crate::config::{Config, CrateConfig, IncludedLicense, load_config, parse_config};
     use crate::file_io::FileIOSpy;
     use crate::licenses::status::LicenseStatus;
+    use crate::licenses::validate::ConfidenceThreshold;
     use std::collections::HashMap;
     use std::path::PathBuf;
 
@@ -167,6 +230,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn config_with_valid_heading_and_preferred_licenses_is_valid() {
+        let contents = r#"
+        [crates.anyhow]
+        prefer = ["Apache-2.0", "MIT"]"#;
+        assert_eq!(
+            vec!["Apache-2.0".to_string(), "MIT".to_string()],
+            parse_config(contents).unwrap().crate_configs["anyhow"].prefer
+        );
+    }
+
+    #[test]
+    fn config_with_valid_heading_and_confidence_threshold_is_valid() {
+        let contents = r"
+        [crates.anyhow]
+        confidence-threshold = 0.6";
+        assert_eq!(
+            Some(ConfidenceThreshold::try_from(0.6).unwrap()),
+            parse_config(contents).unwrap().crate_configs["anyhow"].confidence_threshold
+        );
+    }
+
+    #[test]
+    fn config_with_confidence_threshold_out_of_range_is_invalid() {
+        let contents = r"
+        [global]
+        confidence-threshold = 1.5";
+        assert!(parse_config(contents).is_err());
+    }
+
     #[test]
     fn config_with_multiple_valid_headings_and_multiple_skipped_files() {
         let contents = r#"
@@ -218,7 +311,19 @@ mod tests {
         no-default-features = true
         feature = ["feature"]
         exclude = ["test"]
-        ignore = ["crate1","crate2"]"#;
+        ignore = ["crate1","crate2"]
+        prefer = ["MIT"]
+        license-texts = "texts"
+        scan-content = true
+        scan-license-dirs = true
+        scan-headers = true
+        scan-embedded = true
+        collect-embedded = true
+        sibling-versions = true
+        vendor = "vendor"
+        metadata = "metadata.json"
+        binary = "target/release/example"
+        confidence-threshold = 0.9"#;
         assert_eq!(
             Config {
                 global: GlobalArgs {
@@ -230,6 +335,18 @@ mod tests {
                     feature: vec!["feature".to_string()],
                     exclude: vec!["test".to_string()],
                     ignore: vec!["crate1".to_string(), "crate2".to_string()],
+                    prefer: vec!["MIT".to_string()],
+                    license_texts: Some(PathBuf::from("texts")),
+                    scan_content: true,
+                    scan_license_dirs: true,
+                    scan_headers: true,
+                    scan_embedded: true,
+                    collect_embedded: true,
+                    sibling_versions: true,
+                    confidence_threshold: Some(ConfidenceThreshold::try_from(0.9).unwrap()),
+                    vendor: Some(PathBuf::from("vendor")),
+                    metadata: Some(PathBuf::from("metadata.json")),
+                    binary: Some(PathBuf::from("target/release/example")),
                     config: None,
                 },
                 crate_configs: HashMap::new(),
@@ -249,6 +366,18 @@ mod tests {
             feature: vec!["feature1".to_string()],
             exclude: vec!["test".to_string()],
             ignore: vec![],
+            prefer: vec!["Apache-2.0".to_string()],
+            license_texts: None,
+            scan_content: false,
+            scan_license_dirs: false,
+            scan_headers: false,
+            scan_embedded: false,
+            collect_embedded: false,
+            sibling_versions: false,
+            confidence_threshold: Some(ConfidenceThreshold::try_from(0.7).unwrap()),
+            vendor: None,
+            metadata: None,
+            binary: None,
             config: None,
         };
         let global_args_2 = GlobalArgs {
@@ -260,6 +389,18 @@ mod tests {
             feature: vec!["feature2".to_string()],
             exclude: vec![],
             ignore: vec!["lemon".to_string()],
+            prefer: vec!["MIT".to_string()],
+            license_texts: Some(PathBuf::from("texts")),
+            scan_content: true,
+            scan_license_dirs: true,
+            scan_headers: true,
+            scan_embedded: true,
+            collect_embedded: true,
+            sibling_versions: true,
+            confidence_threshold: Some(ConfidenceThreshold::try_from(0.9).unwrap()),
+            vendor: Some(PathBuf::from("vendor")),
+            metadata: Some(PathBuf::from("metadata.json")),
+            binary: Some(PathBuf::from("target/release/example")),
             config: None,
         };
         global_args_1.merge(global_args_2);
@@ -273,6 +414,18 @@ mod tests {
                 feature: vec!["feature1".to_string(), "feature2".to_string()],
                 exclude: vec!["test".to_string()],
                 ignore: vec!["lemon".to_string()],
+                prefer: vec!["MIT".to_string()],
+                license_texts: Some(PathBuf::from("texts")),
+                scan_content: true,
+                scan_license_dirs: true,
+                scan_headers: true,
+                scan_embedded: true,
+                collect_embedded: true,
+                sibling_versions: true,
+                confidence_threshold: Some(ConfidenceThreshold::try_from(0.9).unwrap()),
+                vendor: Some(PathBuf::from("vendor")),
+                metadata: Some(PathBuf::from("metadata.json")),
+                binary: Some(PathBuf::from("target/release/example")),
                 config: None,
             },
             global_args_1
@@ -300,6 +453,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn vendor_in_config_conflicts_with_options_it_cannot_apply() {
+        for (contents, args) in [
+            (
+                "[global]\nvendor = \"vendor\"\nmetadata = \"metadata.json\"",
+                GlobalArgs::default(),
+            ),
+            (
+                "[global]\nvendor = \"vendor\"",
+                GlobalArgs {
+                    binary: Some(PathBuf::from("binary")),
+                    ..Default::default()
+                },
+            ),
+            (
+                "[global]\nvendor = \"vendor\"\ndev = true",
+                GlobalArgs::default(),
+            ),
+            (
+                "[global]\nvendor = \"vendor\"",
+                GlobalArgs {
+                    feature: vec!["feature".to_string()],
+                    ..Default::default()
+                },
+            ),
+        ] {
+            let file_io_spy = FileIOSpy::default();
+            file_io_spy
+                .read_file
+                .returns
+                .set([Ok(contents.to_string())]);
+
+            let error = load_config(
+                &file_io_spy,
+                GlobalArgs {
+                    config: Some(PathBuf::from("path")),
+                    ..args
+                },
+            )
+            .unwrap_err();
+
+            assert!(error.to_string().starts_with("vendor cannot be used with"));
+        }
+    }
+
+    #[test]
+    fn metadata_conflicts_with_features() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set([Ok("[global]\nall-features = true".to_string())]);
+
+        let error = load_config(
+            &file_io_spy,
+            GlobalArgs {
+                metadata: Some(PathBuf::from("metadata.json")),
+                config: Some(PathBuf::from("path")),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("metadata cannot be used with")
+        );
+    }
+
     #[test]
     fn never_uses_file_io_if_config_path_not_set() {
         let file_io_spy = FileIOSpy::default();
@@ -343,6 +566,7 @@ mod tests {
             skip: skipped.iter().map(ToString::to_string).collect(),
             allow,
             include: included.to_vec(),
+            ..CrateConfig::default()
         }
     }
 
@@ -405,6 +629,16 @@ mod tests {
         assert_eq!(Some(10), args1.depth);
     }
 
+    #[test]
+    fn merge_keeps_config_preferences_when_none_passed() {
+        let mut args1 = GlobalArgs {
+            prefer: vec!["MIT".to_string()],
+            ..Default::default()
+        };
+        args1.merge(GlobalArgs::default());
+        assert_eq!(vec!["MIT".to_string()], args1.prefer);
+    }
+
     #[test]
     fn merge_combines_features() {
         let mut args1 = GlobalArgs {
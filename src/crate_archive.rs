@@ -0,0 +1,255 @@
+use crate::file_io::{DirEntry, MAX_FILE_SIZE, check_size};
+use anyhow::{Context, bail};
+use flate2::read::GzDecoder;
+use itertools::Itertools;
+use std::ffi::OsString;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+pub struct CrateArchive {
+    root: PathBuf,
+    files: Vec<ArchivedFile>,
+}
+
+struct ArchivedFile {
+    path: PathBuf,
+    size: u64,
+    contents: Vec<u8>,
+}
+
+impl CrateArchive {
+    pub fn find(path: &Path) -> Option<(PathBuf, PathBuf)> {
+        path.ancestors().find_map(|root| {
+            let index = root.parent()?;
+            let src = index.parent()?;
+            if src.file_name()? != "src" || root.exists() {
+                return None;
+            }
+            let mut file_name = root.file_name()?.to_os_string();
+            file_name.push(".crate");
+            let archive = src
+                .parent()?
+                .join("cache")
+                .join(index.file_name()?)
+                .join(file_name);
+            archive.is_file().then(|| (root.to_path_buf(), archive))
+        })
+    }
+
+    pub fn open(root: PathBuf, archive: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(archive)
+            .with_context(|| format!("failed to open crate archive {}", archive.display()))?;
+        Self::read(root, file)
+            .with_context(|| format!("failed to read crate archive {}", archive.display()))
+    }
+
+    fn read(root: PathBuf, reader: impl Read) -> anyhow::Result<Self> {
+        let mut files = Vec::new();
+        for entry in tar::Archive::new(GzDecoder::new(reader)).entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            // archived paths start with the `<crate>-<version>` directory
+            let path: PathBuf = entry.path()?.components().skip(1).collect();
+            if path
+                .components()
+                .any(|component| !matches!(component, Component::Normal(_)))
+            {
+                bail!("invalid path {}", path.display());
+            }
+            let size = entry.size();
+            let mut contents = Vec::new();
+            if size <= MAX_FILE_SIZE {
+                entry.read_to_end(&mut contents)?;
+            }
+            files.push(ArchivedFile {
+                path,
+                size,
+                contents,
+            });
+        }
+        Ok(Self { root, files })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn read_dir(&self, path: &Path) -> anyhow::Result<Vec<DirEntry>> {
+        let directory = self.relative(path)?;
+        let entries: Vec<DirEntry> = self
+            .files
+            .iter()
+            .filter_map(|file| {
+                let mut components = file.path.strip_prefix(directory).ok()?.components();
+                let name = components.next()?.as_os_str().to_os_string();
+                Some((name, components.next().is_none()))
+            })
+            .unique()
+            .map(|(name, is_file): (OsString, bool)| DirEntry {
+                path: path.join(&name),
+                name,
+                is_file,
+            })
+            .collect();
+        if entries.is_empty() && !directory.as_os_str().is_empty() {
+            bail!("no such directory in crate archive");
+        }
+        Ok(entries)
+    }
+
+    pub fn read_file(&self, path: &Path) -> anyhow::Result<&[u8]> {
+        let file = self.relative(path)?;
+        let archived = self
+            .files
+            .iter()
+            .find(|archived| archived.path == file)
+            .context("no such file in crate archive")?;
+        check_size(archived.size)?;
+        Ok(&archived.contents)
+    }
+
+    fn relative<'a>(&self, path: &'a Path) -> anyhow::Result<&'a Path> {
+        path.strip_prefix(&self.root)
+            .context("path is outside of the crate archive")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::{FileIO, FileSystem};
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    fn archive_bytes(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for (path, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn crate_archive(files: &[(&str, &str)]) -> CrateArchive {
+        CrateArchive::read(
+            PathBuf::from("src/example-1.0.0"),
+            archive_bytes(files).as_slice(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn archived_files_are_read_relative_to_the_extracted_path() {
+        let archive = crate_archive(&[
+            ("example-1.0.0/LICENSE-MIT", "MIT License"),
+            (
+                "example-1.0.0/src/lib.rs",
+                "// SPDX-License-Identifier: MIT",
+            ),
+            ("example-1.0.0/src/main.rs", ""),
+        ]);
+
+        assert_eq!(
+            vec![
+                DirEntry {
+                    name: OsString::from("LICENSE-MIT"),
+                    path: PathBuf::from("src/example-1.0.0/LICENSE-MIT"),
+                    is_file: true,
+                },
+                DirEntry {
+                    name: OsString::from("src"),
+                    path: PathBuf::from("src/example-1.0.0/src"),
+                    is_file: false,
+                },
+            ],
+            archive.read_dir(Path::new("src/example-1.0.0")).unwrap()
+        );
+        assert_eq!(
+            2,
+            archive
+                .read_dir(Path::new("src/example-1.0.0/src"))
+                .unwrap()
+                .len()
+        );
+        assert_eq!(
+            b"MIT License",
+            archive
+                .read_file(Path::new("src/example-1.0.0/LICENSE-MIT"))
+                .unwrap()
+        );
+        assert!(
+            archive
+                .read_file(Path::new("src/example-1.0.0/LICENSE-APACHE"))
+                .is_err()
+        );
+        assert!(
+            archive
+                .read_dir(Path::new("src/example-1.0.0/missing"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn missing_registry_sources_are_read_from_the_cached_crate_archive() {
+        let registry = tempfile::tempdir().unwrap();
+        let cache = registry.path().join("cache/index");
+        std::fs::create_dir_all(&cache).unwrap();
+        std::fs::write(
+            cache.join("example-1.0.0.crate"),
+            archive_bytes(&[("example-1.0.0/LICENSE", "MIT License")]),
+        )
+        .unwrap();
+        let source = registry.path().join("src/index/example-1.0.0");
+        let file_system = FileSystem::default();
+
+        let entries = file_system.read_dir(&source).unwrap();
+
+        assert_eq!(
+            vec![DirEntry {
+                name: OsString::from("LICENSE"),
+                path: source.join("LICENSE"),
+                is_file: true,
+            }],
+            entries
+        );
+        assert_eq!(
+            "MIT License",
+            file_system.read_file(&entries[0].path).unwrap()
+        );
+        let copied = registry.path().join("copied");
+        file_system.copy_file(&entries[0].path, &copied).unwrap();
+        assert_eq!("MIT License", std::fs::read_to_string(copied).unwrap());
+        assert!(
+            file_system
+                .read_dir(&registry.path().join("src/index/other-1.0.0"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn files_too_large_to_read_are_listed_but_not_read() {
+        let large = "x".repeat(usize::try_from(MAX_FILE_SIZE).unwrap() + 1);
+        let archive = crate_archive(&[("example-1.0.0/LICENSE", &large)]);
+
+        assert_eq!(
+            1,
+            archive
+                .read_dir(Path::new("src/example-1.0.0"))
+                .unwrap()
+                .len()
+        );
+        assert!(archive.files[0].contents.is_empty());
+        assert!(
+            archive
+                .read_file(Path::new("src/example-1.0.0/LICENSE"))
+                .is_err()
+        );
+    }
+}
@@ -1,8 +1,49 @@
-use anyhow::Context;
+use crate::crate_archive::CrateArchive;
+use anyhow::{Context, bail};
+use std::cell::RefCell;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
-pub struct FileSystem {}
+pub const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: [u8; 2] = [0xFF, 0xFE];
+const UTF16_BE_BOM: [u8; 2] = [0xFE, 0xFF];
+
+#[derive(Default)]
+pub struct FileSystem {
+    crate_archive: RefCell<Option<Rc<CrateArchive>>>,
+}
+
+impl FileSystem {
+    fn crate_archive(&self, path: &Path) -> anyhow::Result<Option<Rc<CrateArchive>>> {
+        if path.exists() || path.is_symlink() {
+            return Ok(None);
+        }
+        if let Some(crate_archive) = self
+            .crate_archive
+            .borrow()
+            .as_ref()
+            .filter(|crate_archive| path.starts_with(crate_archive.root()))
+        {
+            return Ok(Some(Rc::clone(crate_archive)));
+        }
+        let Some((root, archive)) = CrateArchive::find(path) else {
+            return Ok(None);
+        };
+        let crate_archive = Rc::new(CrateArchive::open(root, &archive)?);
+        *self.crate_archive.borrow_mut() = Some(Rc::clone(&crate_archive));
+        Ok(Some(crate_archive))
+    }
+
+    fn read_text(&self, path: &Path) -> anyhow::Result<String> {
+        match self.crate_archive(path)? {
+            Some(crate_archive) => decode(crate_archive.read_file(path)?),
+            None => read_text(path),
+        }
+    }
+}
 
 #[cfg_attr(test, autospy::autospy)]
 pub trait FileIO {
@@ -14,6 +55,10 @@ pub trait FileIO {
 
 impl FileIO for FileSystem {
     fn copy_file(&self, from: &Path, to: &Path) -> anyhow::Result<()> {
+        if let Some(crate_archive) = self.crate_archive(from)? {
+            return std::fs::write(to, crate_archive.read_file(from)?)
+                .with_context(|| format!("failed to copy {} to {}", from.display(), to.display()));
+        }
         std::fs::copy(from, to).context(format!(
             "failed to copy {} to {}",
             from.display(),
@@ -23,6 +68,11 @@ impl FileIO for FileSystem {
     }
 
     fn read_dir(&self, path: &Path) -> anyhow::Result<Vec<DirEntry>> {
+        if let Some(crate_archive) = self.crate_archive(path)? {
+            return crate_archive
+                .read_dir(path)
+                .with_context(|| format!("failed to read directory {}", path.display()));
+        }
         std::fs::read_dir(path)
             .with_context(|| format!("failed to read directory {}", path.display()))?
             .map(DirEntry::try_from)
@@ -30,7 +80,7 @@ impl FileIO for FileSystem {
     }
 
     fn read_file(&self, path: &Path) -> anyhow::Result<String> {
-        std::fs::read_to_string(path)
+        self.read_text(path)
             .with_context(|| format!("failed to read file {}", path.display()))
     }
 
@@ -40,6 +90,67 @@ impl FileIO for FileSystem {
     }
 }
 
+fn read_text(path: &Path) -> anyhow::Result<String> {
+    let metadata = std::fs::metadata(path).map_err(|error| {
+        if path.is_symlink() {
+            anyhow::anyhow!("symbolic link does not resolve to a file: {error}")
+        } else {
+            error.into()
+        }
+    })?;
+    if !metadata.is_file() {
+        bail!("not a file");
+    }
+    check_size(metadata.len())?;
+    decode(&std::fs::read(path)?)
+}
+
+pub fn check_size(size: u64) -> anyhow::Result<()> {
+    if size > MAX_FILE_SIZE {
+        bail!("file is too large ({size} bytes, the limit is {MAX_FILE_SIZE} bytes)");
+    }
+    Ok(())
+}
+
+pub fn decode(bytes: &[u8]) -> anyhow::Result<String> {
+    if let Some(bytes) = bytes.strip_prefix(&UTF16_LE_BOM) {
+        return decode_utf16(bytes, u16::from_le_bytes);
+    }
+    if let Some(bytes) = bytes.strip_prefix(&UTF16_BE_BOM) {
+        return decode_utf16(bytes, u16::from_be_bytes);
+    }
+    // ASCII text in UTF-16 without a byte order mark has every other byte zero
+    if bytes.len() >= 2 && bytes.len().is_multiple_of(2) {
+        if bytes.iter().skip(1).step_by(2).all(|&byte| byte == 0) {
+            return decode_utf16(bytes, u16::from_le_bytes);
+        }
+        if bytes.iter().step_by(2).all(|&byte| byte == 0) {
+            return decode_utf16(bytes, u16::from_be_bytes);
+        }
+    }
+    let bytes = bytes.strip_prefix(&UTF8_BOM).unwrap_or(bytes);
+    if bytes.contains(&0) {
+        bail!("binary file");
+    }
+    Ok(String::from_utf8(bytes.to_vec())
+        .unwrap_or_else(|_| bytes.iter().copied().map(char::from).collect()))
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> anyhow::Result<String> {
+    if !bytes.len().is_multiple_of(2) {
+        bail!("invalid UTF-16, odd number of bytes");
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]))
+        .collect();
+    let text = String::from_utf16(&units).context("invalid UTF-16")?;
+    if text.contains('\0') {
+        bail!("binary file");
+    }
+    Ok(text)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DirEntry {
     pub name: OsString,
@@ -50,10 +161,52 @@ pub struct DirEntry {
 impl DirEntry {
     fn try_from(dir_entry: std::io::Result<std::fs::DirEntry>) -> anyhow::Result<Self> {
         let dir_entry = dir_entry.context("invalid dir entry")?;
+        let path = dir_entry.path();
         Ok(Self {
             name: dir_entry.file_name(),
-            path: dir_entry.path(),
-            is_file: dir_entry.path().is_file(),
+            // broken symbolic links are kept as files, so reading them reports the broken link
+            is_file: path.is_file() || path.is_symlink() && !path.is_dir(),
+            path,
         })
     }
+
+    // the link may point back up the tree, so is not searched recursively
+    pub fn is_linked_directory(&self) -> bool {
+        !self.is_file && self.path.is_symlink()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(text: &str, to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+        text.encode_utf16().flat_map(to_bytes).collect()
+    }
+
+    #[test]
+    fn utf8_is_decoded_without_byte_order_mark() {
+        assert_eq!("MIT ©", decode("MIT ©".as_bytes()).unwrap());
+        assert_eq!("MIT", decode(b"\xEF\xBB\xBFMIT").unwrap());
+    }
+
+    #[test]
+    fn utf16_is_decoded() {
+        let little_endian = [UTF16_LE_BOM.to_vec(), utf16("MIT ©", u16::to_le_bytes)].concat();
+        let big_endian = [UTF16_BE_BOM.to_vec(), utf16("MIT ©", u16::to_be_bytes)].concat();
+
+        assert_eq!("MIT ©", decode(&little_endian).unwrap());
+        assert_eq!("MIT ©", decode(&big_endian).unwrap());
+        assert_eq!("MIT", decode(&utf16("MIT", u16::to_le_bytes)).unwrap());
+    }
+
+    #[test]
+    fn invalid_utf8_is_decoded_as_latin1() {
+        assert_eq!("Copyright © 2024", decode(b"Copyright \xA9 2024").unwrap());
+    }
+
+    #[test]
+    fn binary_files_are_not_decoded() {
+        assert!(decode(b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR").is_err());
+    }
 }
@@ -2,17 +2,23 @@ use crate::cargo_metadata::Package;
 use crate::config::CrateConfig;
 use crate::file_io::{DirEntry, FileIO};
 use crate::licenses::License;
+use crate::licenses::headers::{scan_source_licenses, with_source_licenses};
+use crate::licenses::siblings::with_sibling_version;
 use crate::licenses::status::LicenseStatus;
 use crate::licenses::status::LicenseStatuses;
-use crate::licenses::validate::validate_licenses;
+use crate::licenses::texts::LicenseTexts;
+use crate::licenses::validate::{ConfidenceThreshold, validate_licenses};
 use crate::log::ProgressBar;
 use std::collections::HashMap;
 
 pub fn check_licenses(
     file_io: &impl FileIO,
     progress_bar: &impl ProgressBar,
+    license_texts: &LicenseTexts,
+    confidence_threshold: ConfidenceThreshold,
     all_licenses: &HashMap<Package, Vec<DirEntry>>,
     crate_configs: &HashMap<String, CrateConfig>,
+    scan_headers: bool,
 ) -> LicenseStatuses {
     progress_bar.set_len(all_licenses.len() as u64);
     let statuses = LicenseStatuses(
@@ -23,10 +29,13 @@ pub fn check_licenses(
                 (
                     package.clone(),
                     license_status_after_allowed(
-                        validate_licenses(
+                        package_status(
                             file_io,
-                            package.license.as_deref().map(License::parse).as_ref(),
+                            license_texts,
+                            confidence_threshold.for_package(package, crate_configs),
+                            package,
                             licenses,
+                            scan_headers,
                         ),
                         package,
                         crate_configs,
@@ -39,7 +48,38 @@ pub fn check_licenses(
     statuses
 }
 
-fn license_status_after_allowed(
+pub fn package_status(
+    file_io: &impl FileIO,
+    license_texts: &LicenseTexts,
+    confidence_threshold: ConfidenceThreshold,
+    package: &Package,
+    licenses: &[DirEntry],
+    scan_headers: bool,
+) -> LicenseStatus {
+    let declared = package.license.as_deref().map(License::parse);
+    let license_status = with_sibling_version(
+        validate_licenses(
+            file_io,
+            license_texts,
+            confidence_threshold,
+            declared.as_ref(),
+            licenses,
+        ),
+        package,
+        licenses,
+    );
+    if scan_headers {
+        with_source_licenses(
+            license_status,
+            declared.as_ref(),
+            &scan_source_licenses(file_io, package),
+        )
+    } else {
+        license_status
+    }
+}
+
+pub fn license_status_after_allowed(
     license_status: LicenseStatus,
     package: &Package,
     crate_configs: &HashMap<String, CrateConfig>,
@@ -60,7 +100,8 @@ mod tests {
     use crate::file_io::{DirEntry, FileIOSpy};
     use crate::licenses::check::check_licenses;
     use crate::licenses::status::{LicenseStatus, LicenseStatuses};
-    use crate::licenses::validate::LICENSE_TEXTS;
+    use crate::licenses::texts::{LICENSE_TEXTS, LicenseTexts};
+    use crate::licenses::validate::ConfidenceThreshold;
     use crate::log::ProgressBarSpy;
     use cargo_metadata::camino::Utf8PathBuf;
     use std::collections::HashMap;
@@ -83,6 +124,8 @@ mod tests {
                     path: Utf8PathBuf::default(),
                     url: None,
                     license: None,
+                    authors: Vec::new(),
+                    source: None,
                 },
                 vec![],
             ),
@@ -92,6 +135,8 @@ mod tests {
                     path: Utf8PathBuf::default(),
                     url: None,
                     license: Some("MIT".to_string()),
+                    authors: Vec::new(),
+                    source: None,
                 },
                 vec![DirEntry {
                     name: OsString::from("LICENSE"),
@@ -112,6 +157,8 @@ mod tests {
                         path: Utf8PathBuf::default(),
                         url: None,
                         license: Some("MIT".to_string()),
+                        authors: Vec::new(),
+                        source: None,
                     },
                     LicenseStatus::Valid,
                 ),
@@ -125,8 +172,11 @@ mod tests {
             check_licenses(
                 &file_io_spy,
                 &progress_bar_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 &all_licenses,
-                &HashMap::new()
+                &HashMap::new(),
+                false
             )
         );
 
@@ -148,6 +198,8 @@ mod tests {
                 path: Utf8PathBuf::default(),
                 url: None,
                 license: Some("MIT".to_string()),
+                authors: Vec::new(),
+                source: None,
             },
             vec![],
         ))
@@ -158,8 +210,11 @@ mod tests {
             check_licenses(
                 &file_io_spy,
                 &progress_bar_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 &all_licenses,
-                &HashMap::new()
+                &HashMap::new(),
+                false
             )
             .any_invalid()
         );
@@ -170,13 +225,23 @@ mod tests {
                 skip: vec![],
                 allow: Some(LicenseStatus::TooFew),
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
 
         // errors when allowed status is incorrect
         assert!(
-            check_licenses(&file_io_spy, &progress_bar_spy, &all_licenses, &config).any_invalid()
+            check_licenses(
+                &file_io_spy,
+                &progress_bar_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                &all_licenses,
+                &config,
+                false
+            )
+            .any_invalid()
         );
 
         let config = std::iter::once((
@@ -185,13 +250,23 @@ mod tests {
                 skip: vec![],
                 allow: Some(LicenseStatus::Empty),
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
 
         // fine when status is allowed
         assert!(
-            !check_licenses(&file_io_spy, &progress_bar_spy, &all_licenses, &config).any_invalid()
+            !check_licenses(
+                &file_io_spy,
+                &progress_bar_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                &all_licenses,
+                &config,
+                false
+            )
+            .any_invalid()
         );
     }
 
@@ -219,6 +294,8 @@ mod tests {
                 path: Utf8PathBuf::default(),
                 url: None,
                 license: Some("MIT/Apache-2.0".to_string()),
+                authors: Vec::new(),
+                source: None,
             },
             vec![
                 DirEntry {
@@ -250,20 +327,102 @@ mod tests {
             CrateConfig {
                 skip: vec![],
                 allow: Some(LicenseStatus::Additional(vec![
-                    "LICENSE".to_string(),
-                    "LICENSE.BSD-3-Clause".to_string(),
+                    "LICENSE".into(),
+                    "LICENSE.BSD-3-Clause".into(),
                 ])),
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
 
         assert!(
-            !check_licenses(&file_io_spy, &progress_bar_spy, &all_licenses, &config).any_invalid()
+            !check_licenses(
+                &file_io_spy,
+                &progress_bar_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                &all_licenses,
+                &config,
+                false
+            )
+            .any_invalid()
+        );
+    }
+
+    #[test]
+    fn undeclared_source_license_is_invalid_unless_allowed() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            Ok(if path.ends_with("LICENSE") {
+                license_text("MIT")
+            } else {
+                "// SPDX-License-Identifier: GPL-3.0-only".to_string()
+            })
+        });
+        file_io_spy.read_dir.returns.set_fn(|_: &PathBuf| {
+            Ok(vec![DirEntry {
+                name: OsString::from("vendored.c"),
+                path: PathBuf::from("vendored.c"),
+                is_file: true,
+            }])
+        });
+        let progress_bar_spy = ProgressBarSpy::default();
+        progress_bar_spy.set_len.returns.set_fn(|_| ());
+        progress_bar_spy.increment.returns.set_fn(|()| ());
+        progress_bar_spy.finish.returns.set_fn(|()| ());
+
+        let package = Package {
+            normalised_name: "example".to_string(),
+            path: Utf8PathBuf::default(),
+            url: None,
+            license: Some("MIT".to_string()),
+            authors: Vec::new(),
+            source: None,
+        };
+        let all_licenses = std::iter::once((
+            package.clone(),
+            vec![DirEntry {
+                name: OsString::from("LICENSE"),
+                path: PathBuf::from("LICENSE"),
+                is_file: true,
+            }],
+        ))
+        .collect();
+        let config = std::iter::once((
+            "example".to_string(),
+            CrateConfig {
+                allow: Some(LicenseStatus::UndeclaredSourceLicense(vec![
+                    "GPL-3.0-only".to_string(),
+                ])),
+                ..CrateConfig::default()
+            },
+        ))
+        .collect();
+
+        let no_config = HashMap::new();
+        let check = |crate_configs| {
+            check_licenses(
+                &file_io_spy,
+                &progress_bar_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                &all_licenses,
+                crate_configs,
+                true,
+            )
+        };
+
+        assert_eq!(
+            LicenseStatus::UndeclaredSourceLicense(vec!["GPL-3.0-only".to_string()]),
+            check(&no_config).0[&package]
         );
+        assert!(!check(&config).any_invalid());
     }
 
     fn license_text(id: &str) -> String {
-        LICENSE_TEXTS.get(id).unwrap().to_string()
+        LICENSE_TEXTS[id]
+            .replace("<year> <copyright holders>", "2024 Example")
+            .replace("[yyyy] [name of copyright owner]", "2024 Example")
     }
 }
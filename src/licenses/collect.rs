@@ -2,17 +2,31 @@ use crate::cargo_metadata::Package;
 use crate::config::CrateConfig;
 use crate::file_io::DirEntry;
 use crate::file_io::FileIO;
-use crate::licenses::is_license::is_license;
+use crate::licenses::content::ContentScan;
+use crate::licenses::inherited::root_licenses;
+use crate::licenses::is_license::{is_license, is_license_directory};
+use crate::licenses::notice_files::is_notice_file;
+use crate::licenses::siblings::sibling_licenses;
+use crate::licenses::texts::Identified;
 use std::collections::HashMap;
+use std::ffi::OsString;
+
+#[derive(Default)]
+pub struct Discovery<'a> {
+    pub content_scan: Option<ContentScan<'a>>,
+    pub license_directories: bool,
+    pub sibling_versions: bool,
+}
 
 pub fn collect_licenses(
     file_io: &impl FileIO,
     packages: &[Package],
     crate_configs: &HashMap<String, CrateConfig>,
+    discovery: &Discovery,
 ) -> anyhow::Result<HashMap<Package, Vec<DirEntry>>> {
     packages
         .iter()
-        .map(|package| collect_licenses_for_package(file_io, package, crate_configs))
+        .map(|package| collect_licenses_for_package(file_io, package, crate_configs, discovery))
         .collect()
 }
 
@@ -20,21 +34,122 @@ fn collect_licenses_for_package(
     file_io: &impl FileIO,
     package: &Package,
     crate_configs: &HashMap<String, CrateConfig>,
+    discovery: &Discovery,
 ) -> anyhow::Result<(Package, Vec<DirEntry>)> {
-    Ok((
-        package.clone(),
-        file_io
-            .read_dir(package.path.as_ref())?
-            .into_iter()
-            .filter(is_license)
-            .filter(|dir_entry| {
-                !is_skipped_file(dir_entry, skipped_files_for_package(package, crate_configs))
-            })
-            .collect(),
-    ))
+    let licenses = package_files(file_io, package, crate_configs, discovery)?
+        .into_iter()
+        .filter(|(_, collected)| collected.is_license())
+        .map(|(dir_entry, _)| dir_entry)
+        .collect();
+    Ok((package.clone(), licenses))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Collected {
+    License,
+    Detected(Identified),
+    Inherited,
+    SiblingVersion,
+    Skipped,
+    Notice,
+    NotAFile,
+    NotALicenseName,
+}
+
+impl Collected {
+    pub const fn is_license(&self) -> bool {
+        matches!(
+            self,
+            Self::License | Self::Detected(_) | Self::Inherited | Self::SiblingVersion
+        )
+    }
+}
+
+pub fn package_files(
+    file_io: &impl FileIO,
+    package: &Package,
+    crate_configs: &HashMap<String, CrateConfig>,
+    discovery: &Discovery,
+) -> anyhow::Result<Vec<(DirEntry, Collected)>> {
+    let skipped_files = skipped_files_for_package(package, crate_configs);
+    let mut entries = file_io.read_dir(package.path.as_ref())?;
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut files = Vec::new();
+    for dir_entry in entries.iter().cloned() {
+        let collected = if is_skipped_file(&dir_entry, skipped_files) {
+            Collected::Skipped
+        } else if is_notice_file(&dir_entry) {
+            Collected::Notice
+        } else if discovery.license_directories && is_license_directory(&dir_entry) {
+            for dir_entry in license_directory_files(file_io, &dir_entry)? {
+                let collected = if is_skipped_file(&dir_entry, skipped_files) {
+                    Collected::Skipped
+                } else {
+                    Collected::License
+                };
+                files.push((dir_entry, collected));
+            }
+            continue;
+        } else if !dir_entry.is_file {
+            Collected::NotAFile
+        } else if is_license(&dir_entry) {
+            Collected::License
+        } else if let Some(detected) = discovery.content_scan.as_ref().and_then(|content_scan| {
+            content_scan
+                .for_package(package, crate_configs)
+                .detect_license(file_io, &dir_entry)
+        }) {
+            Collected::Detected(detected)
+        } else {
+            Collected::NotALicenseName
+        };
+        files.push((dir_entry, collected));
+    }
+
+    if !files.iter().any(|(_, collected)| collected.is_license()) {
+        if package.is_git_or_path() {
+            files.extend(
+                root_licenses(file_io, package, &entries, skipped_files)
+                    .into_iter()
+                    .map(|dir_entry| (dir_entry, Collected::Inherited)),
+            );
+        } else if discovery.sibling_versions {
+            files.extend(
+                sibling_licenses(file_io, package, skipped_files)
+                    .into_iter()
+                    .map(|dir_entry| (dir_entry, Collected::SiblingVersion)),
+            );
+        }
+    }
+    Ok(files)
 }
 
-fn skipped_files_for_package<'a>(
+pub fn license_directory_files(
+    file_io: &impl FileIO,
+    directory: &DirEntry,
+) -> anyhow::Result<Vec<DirEntry>> {
+    let mut files = Vec::new();
+    for dir_entry in file_io.read_dir(&directory.path)? {
+        let dir_entry = DirEntry {
+            name: OsString::from(format!(
+                "{}/{}",
+                directory.name.to_string_lossy(),
+                dir_entry.name.to_string_lossy()
+            )),
+            ..dir_entry
+        };
+        if dir_entry.is_file {
+            files.push(dir_entry);
+        } else if !dir_entry.is_linked_directory() {
+            files.extend(license_directory_files(file_io, &dir_entry)?);
+        }
+    }
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}
+
+pub fn skipped_files_for_package<'a>(
     package: &'a Package,
     crate_configs: &'a HashMap<String, CrateConfig>,
 ) -> &'a [String] {
@@ -43,7 +158,7 @@ fn skipped_files_for_package<'a>(
         .map_or(&[], |config| config.skip.as_slice())
 }
 
-fn is_skipped_file(dir_entry: &DirEntry, skipped_files: &[String]) -> bool {
+pub fn is_skipped_file(dir_entry: &DirEntry, skipped_files: &[String]) -> bool {
     dir_entry
         .name
         .to_str()
@@ -54,8 +169,12 @@ fn is_skipped_file(dir_entry: &DirEntry, skipped_files: &[String]) -> bool {
 mod tests {
     use crate::cargo_metadata::Package;
     use crate::config::CrateConfig;
-    use crate::file_io::{DirEntry, FileIOSpy};
-    use crate::licenses::collect::collect_licenses;
+    use crate::file_io::{DirEntry, FileIOSpy, FileSystem};
+    use crate::licenses::collect::{Discovery, collect_licenses};
+    use crate::licenses::content::ContentScan;
+    use crate::licenses::texts::{LICENSE_TEXTS, LicenseTexts};
+    use crate::licenses::validate::ConfidenceThreshold;
+    use cargo_metadata::camino::Utf8PathBuf;
     use std::collections::HashMap;
     use std::ffi::OsString;
     use std::path::PathBuf;
@@ -69,7 +188,13 @@ mod tests {
             .set([Err(anyhow::anyhow!("deliberate test error"))]);
 
         assert!(
-            collect_licenses(&file_io_spy, &[Package::called("example")], &HashMap::new()).is_err()
+            collect_licenses(
+                &file_io_spy,
+                &[Package::called("example")],
+                &HashMap::new(),
+                &Discovery::default()
+            )
+            .is_err()
         );
     }
 
@@ -83,7 +208,13 @@ mod tests {
 
         assert_eq!(
             expected_licenses,
-            collect_licenses(&file_io_spy, &[Package::called("example")], &HashMap::new()).unwrap()
+            collect_licenses(
+                &file_io_spy,
+                &[Package::called("example")],
+                &HashMap::new(),
+                &Discovery::default()
+            )
+            .unwrap()
         );
     }
 
@@ -101,7 +232,13 @@ mod tests {
 
         assert_eq!(
             expected_licenses,
-            collect_licenses(&file_io_spy, &[Package::called("example")], &HashMap::new()).unwrap()
+            collect_licenses(
+                &file_io_spy,
+                &[Package::called("example")],
+                &HashMap::new(),
+                &Discovery::default()
+            )
+            .unwrap()
         );
     }
 
@@ -123,7 +260,13 @@ mod tests {
 
         assert_eq!(
             expected_licenses,
-            collect_licenses(&file_io_spy, &[Package::called("example")], &HashMap::new()).unwrap()
+            collect_licenses(
+                &file_io_spy,
+                &[Package::called("example")],
+                &HashMap::new(),
+                &Discovery::default()
+            )
+            .unwrap()
         );
     }
 
@@ -142,6 +285,7 @@ mod tests {
                 skip: vec!["LICENSE".to_string()],
                 allow: None,
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
@@ -151,7 +295,13 @@ mod tests {
 
         assert_eq!(
             expected_licenses,
-            collect_licenses(&file_io_spy, &[Package::called("example")], &skipped_files).unwrap()
+            collect_licenses(
+                &file_io_spy,
+                &[Package::called("example")],
+                &skipped_files,
+                &Discovery::default()
+            )
+            .unwrap()
         );
     }
 
@@ -182,6 +332,7 @@ mod tests {
                 skip: vec!["COPYRIGHT".to_string(), "LICENSE-APACHE".to_string()],
                 allow: None,
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
@@ -198,7 +349,13 @@ mod tests {
 
         assert_eq!(
             expected_licenses,
-            collect_licenses(&file_io_spy, &[Package::called("example")], &skipped_files).unwrap()
+            collect_licenses(
+                &file_io_spy,
+                &[Package::called("example")],
+                &skipped_files,
+                &Discovery::default()
+            )
+            .unwrap()
         );
     }
 
@@ -224,6 +381,7 @@ mod tests {
             &file_io_spy,
             &[Package::called("alpha"), Package::called("beta")],
             &HashMap::new(),
+            &Discovery::default(),
         )
         .unwrap();
 
@@ -251,6 +409,7 @@ mod tests {
                 skip: vec!["COPYING".to_string()],
                 allow: None,
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
@@ -259,6 +418,7 @@ mod tests {
             &file_io_spy,
             &[Package::called("alpha"), Package::called("beta")],
             &skipped_files,
+            &Discovery::default(),
         )
         .unwrap();
 
@@ -266,4 +426,230 @@ mod tests {
         assert!(result[&Package::called("alpha")].is_empty());
         assert_eq!(vec![license], result[&Package::called("beta")]);
     }
+
+    #[test]
+    fn content_scan_collects_license_text_with_other_name() {
+        let file_io_spy = FileIOSpy::default();
+        let legal = DirEntry {
+            name: OsString::from("LEGAL"),
+            path: PathBuf::from("LEGAL"),
+            is_file: true,
+        };
+        let readme = DirEntry {
+            name: OsString::from("README.md"),
+            path: PathBuf::from("README.md"),
+            is_file: true,
+        };
+        file_io_spy
+            .read_dir
+            .returns
+            .set([Ok(vec![legal.clone(), readme])]);
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            if path.ends_with("LEGAL") {
+                Ok(LICENSE_TEXTS["MIT"].to_string())
+            } else {
+                Ok("# example\n\nAn example crate.".to_string())
+            }
+        });
+
+        let result = collect_licenses(
+            &file_io_spy,
+            &[Package::called("example")],
+            &HashMap::new(),
+            &Discovery {
+                content_scan: Some(ContentScan {
+                    license_texts: &LicenseTexts::default(),
+                    confidence_threshold: ConfidenceThreshold::default(),
+                }),
+                ..Discovery::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(vec![legal], result[&Package::called("example")]);
+    }
+
+    #[test]
+    fn license_directories_are_searched_recursively() {
+        let file_io_spy = FileIOSpy::default();
+        let directory = |name: &str| DirEntry {
+            name: OsString::from(name),
+            path: PathBuf::from(name),
+            is_file: false,
+        };
+        let file = |name: &str| DirEntry {
+            name: OsString::from(name),
+            path: PathBuf::from(name),
+            is_file: true,
+        };
+        file_io_spy.read_dir.returns.set([
+            Ok(vec![directory("LICENSES"), directory("src")]),
+            Ok(vec![file("MIT.txt"), directory("extra")]),
+            Ok(vec![file("Apache-2.0.txt")]),
+        ]);
+
+        let result = collect_licenses(
+            &file_io_spy,
+            &[Package::called("example")],
+            &HashMap::new(),
+            &Discovery {
+                license_directories: true,
+                ..Discovery::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                OsString::from("LICENSES/MIT.txt"),
+                OsString::from("LICENSES/extra/Apache-2.0.txt")
+            ],
+            result[&Package::called("example")]
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symbolic_links_within_license_directories_are_not_followed() {
+        let crate_dir = tempfile::tempdir().unwrap();
+        let licenses = crate_dir.path().join("LICENSES");
+        std::fs::create_dir(&licenses).unwrap();
+        std::fs::write(licenses.join("MIT.txt"), LICENSE_TEXTS["MIT"]).unwrap();
+        std::os::unix::fs::symlink("..", licenses.join("loop")).unwrap();
+        let package = Package {
+            path: Utf8PathBuf::try_from(crate_dir.path().to_path_buf()).unwrap(),
+            ..Package::called("example")
+        };
+
+        let result = collect_licenses(
+            &FileSystem::default(),
+            std::slice::from_ref(&package),
+            &HashMap::new(),
+            &Discovery {
+                license_directories: true,
+                ..Discovery::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![OsString::from("LICENSES/MIT.txt")],
+            result[&package]
+                .iter()
+                .map(|entry| entry.name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn license_directories_are_not_searched_by_default() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set([Ok(vec![DirEntry {
+            name: OsString::from("LICENSES"),
+            path: PathBuf::from("LICENSES"),
+            is_file: false,
+        }])]);
+
+        let result = collect_licenses(
+            &file_io_spy,
+            &[Package::called("example")],
+            &HashMap::new(),
+            &Discovery::default(),
+        )
+        .unwrap();
+
+        assert!(result[&Package::called("example")].is_empty());
+        assert_eq!(1, file_io_spy.read_dir.arguments.take().len());
+    }
+
+    #[test]
+    fn git_and_path_dependencies_without_licenses_inherit_root_licenses() {
+        let root_license = DirEntry {
+            name: OsString::from("LICENSE"),
+            path: PathBuf::from("repo/LICENSE"),
+            is_file: true,
+        };
+        // cargo marks its git checkouts as the root of the repository
+        let checkout_marker = DirEntry {
+            name: OsString::from(".cargo-ok"),
+            path: PathBuf::from("repo/.cargo-ok"),
+            is_file: true,
+        };
+        let file_io_spy = FileIOSpy::default();
+        let returned_license = root_license.clone();
+        file_io_spy.read_dir.returns.set_fn(move |path: &PathBuf| {
+            Ok(if path == &PathBuf::from("repo") {
+                vec![checkout_marker.clone(), returned_license.clone()]
+            } else {
+                Vec::new()
+            })
+        });
+        let git = Package {
+            path: Utf8PathBuf::from("repo/example"),
+            source: Some("git+https://example.com/repo".to_string()),
+            ..Package::called("git")
+        };
+        let registry = Package {
+            path: Utf8PathBuf::from("repo/example"),
+            source: Some("registry+https://github.com/rust-lang/crates.io-index".to_string()),
+            ..Package::called("registry")
+        };
+
+        let result = collect_licenses(
+            &file_io_spy,
+            &[git.clone(), registry.clone()],
+            &HashMap::new(),
+            &Discovery::default(),
+        )
+        .unwrap();
+
+        assert_eq!(vec![root_license], result[&git]);
+        assert!(result[&registry].is_empty());
+    }
+
+    #[test]
+    fn sibling_versions_are_only_searched_when_enabled() {
+        let sibling_license = DirEntry {
+            name: OsString::from("LICENSE"),
+            path: PathBuf::from("registry/example-1.0.1/LICENSE"),
+            is_file: true,
+        };
+        let file_io_spy = FileIOSpy::default();
+        let returned_license = sibling_license.clone();
+        file_io_spy.read_dir.returns.set_fn(move |path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "registry" => vec![DirEntry {
+                    name: OsString::from("example-1.0.1"),
+                    path: PathBuf::from("registry/example-1.0.1"),
+                    is_file: false,
+                }],
+                "registry/example-1.0.1" => vec![returned_license.clone()],
+                _ => Vec::new(),
+            })
+        });
+        let package = Package {
+            path: Utf8PathBuf::from("registry/example-1.0.0"),
+            source: Some("registry+https://github.com/rust-lang/crates.io-index".to_string()),
+            ..Package::called("example")
+        };
+
+        let collect = |sibling_versions| {
+            collect_licenses(
+                &file_io_spy,
+                std::slice::from_ref(&package),
+                &HashMap::new(),
+                &Discovery {
+                    sibling_versions,
+                    ..Discovery::default()
+                },
+            )
+            .unwrap()
+        };
+
+        assert!(collect(false)[&package].is_empty());
+        assert_eq!(vec![sibling_license], collect(true)[&package]);
+    }
 }
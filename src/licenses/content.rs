@@ -0,0 +1,176 @@
+use crate::cargo_metadata::Package;
+use crate::config::CrateConfig;
+use crate::file_io::{DirEntry, FileIO};
+use crate::licenses::markup::plain_text;
+use crate::licenses::texts::{Identified, LicenseTexts};
+use crate::licenses::validate::ConfidenceThreshold;
+use spdx::detection::TextData;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::Path;
+
+const TEXT_EXTENSIONS: [&str; 6] = ["md", "markdown", "txt", "rst", "html", "htm"];
+const LICENSE_HEADINGS: [&str; 3] = ["licen", "copying", "copyright"];
+
+#[derive(Clone, Copy)]
+pub struct ContentScan<'a> {
+    pub license_texts: &'a LicenseTexts,
+    pub confidence_threshold: ConfidenceThreshold,
+}
+
+impl ContentScan<'_> {
+    pub fn for_package(
+        self,
+        package: &Package,
+        crate_configs: &HashMap<String, CrateConfig>,
+    ) -> Self {
+        Self {
+            confidence_threshold: self
+                .confidence_threshold
+                .for_package(package, crate_configs),
+            ..self
+        }
+    }
+
+    pub fn detect_license(
+        &self,
+        file_io: &impl FileIO,
+        dir_entry: &DirEntry,
+    ) -> Option<Identified> {
+        if !dir_entry.is_file || !is_text_file(&dir_entry.path) {
+            return None;
+        }
+        let contents = file_io.read_file(&dir_entry.path).ok()?;
+
+        std::iter::once(contents.as_str())
+            .chain(license_sections(&contents))
+            .filter_map(|text| {
+                self.license_texts
+                    .identify(&TextData::new(&plain_text(text)))
+            })
+            .filter(|identified| self.confidence_threshold.is_met_by(identified.score))
+            .max_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal))
+    }
+}
+
+pub fn is_text_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_none_or(|extension| {
+            TEXT_EXTENSIONS
+                .iter()
+                .any(|text_extension| extension.eq_ignore_ascii_case(text_extension))
+        })
+}
+
+fn license_sections(contents: &str) -> impl Iterator<Item = &str> {
+    let mut section_starts: Vec<usize> = contents
+        .match_indices('\n')
+        .map(|(index, _)| index + 1)
+        .filter(|&start| contents[start..].starts_with('#'))
+        .collect();
+    if contents.starts_with('#') {
+        section_starts.insert(0, 0);
+    }
+
+    let section_ends: Vec<usize> = section_starts
+        .iter()
+        .skip(1)
+        .copied()
+        .chain(std::iter::once(contents.len()))
+        .collect();
+
+    section_starts
+        .into_iter()
+        .zip(section_ends)
+        .map(|(start, end)| &contents[start..end])
+        .filter(|section| {
+            let heading = section.lines().next().unwrap_or_default().to_lowercase();
+            LICENSE_HEADINGS
+                .iter()
+                .any(|license_heading| heading.contains(license_heading))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::FileIOSpy;
+    use crate::licenses::texts::LICENSE_TEXTS;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    fn file(name: &str) -> DirEntry {
+        DirEntry {
+            name: OsString::from(name),
+            path: PathBuf::from(name),
+            is_file: true,
+        }
+    }
+
+    fn detect(file_io: &FileIOSpy, dir_entry: &DirEntry) -> Option<Identified> {
+        ContentScan {
+            license_texts: &LicenseTexts::default(),
+            confidence_threshold: ConfidenceThreshold::default(),
+        }
+        .detect_license(file_io, dir_entry)
+    }
+
+    #[test]
+    fn file_containing_license_text_is_detected() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set([Ok(LICENSE_TEXTS["MIT"].to_string())]);
+
+        assert_eq!(
+            Some("MIT".to_string()),
+            detect(&file_io_spy, &file("LEGAL")).map(|identified| identified.id)
+        );
+    }
+
+    #[test]
+    fn license_section_of_readme_is_detected() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set([Ok(format!(
+            "# example\n\nAn example crate with a long description.\n\n## License\n\n{}",
+            LICENSE_TEXTS["MIT"]
+        ))]);
+
+        assert_eq!(
+            Some("MIT".to_string()),
+            detect(&file_io_spy, &file("README.md")).map(|identified| identified.id)
+        );
+    }
+
+    #[test]
+    fn file_without_license_text_is_not_detected() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set([Ok("# example\n\nAn example crate.".to_string())]);
+
+        assert!(detect(&file_io_spy, &file("README.md")).is_none());
+    }
+
+    #[test]
+    fn non_text_files_and_directories_are_not_read() {
+        let file_io_spy = FileIOSpy::default();
+
+        assert!(detect(&file_io_spy, &file("Cargo.toml")).is_none());
+        assert!(
+            detect(
+                &file_io_spy,
+                &DirEntry {
+                    name: OsString::from("src"),
+                    path: PathBuf::from("src"),
+                    is_file: false,
+                }
+            )
+            .is_none()
+        );
+        assert!(file_io_spy.read_file.arguments.take().is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use crate::cargo_metadata::Package;
 use crate::config::{CrateConfig, IncludedLicense};
 use crate::file_io::{DirEntry, FileIO};
-use anyhow::Context;
+use crate::licenses::embedded::EmbeddedLicenses;
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -29,20 +29,44 @@ fn copy_licenses_to_output_folder(
     for license in licenses {
         file_io.copy_file(
             &license.path,
-            &output_folder.join(format!(
-                "{}-{}",
-                package.normalised_name,
-                license
-                    .path
-                    .file_name()
-                    .context("license name contained invalid UTF-8")?
-                    .to_string_lossy()
-            )),
+            &output_folder.join(output_file_name(package, license)),
         )?;
     }
     Ok(())
 }
 
+pub fn output_file_name(package: &Package, license: &DirEntry) -> String {
+    format!(
+        "{}-{}",
+        package.normalised_name,
+        license.name.to_string_lossy().replace('/', "-")
+    )
+}
+
+pub fn embedded_output_file_name(package: &Package, license: &DirEntry) -> String {
+    format!(
+        "{}-embedded-{}",
+        package.normalised_name,
+        license.name.to_string_lossy().replace('/', "-")
+    )
+}
+
+pub fn copy_embedded_licenses(
+    file_io: &impl FileIO,
+    embedded_licenses: &EmbeddedLicenses,
+    output_folder: &Path,
+) -> anyhow::Result<()> {
+    for (package, licenses) in &embedded_licenses.0 {
+        for license in licenses {
+            file_io.copy_file(
+                &license.path,
+                &output_folder.join(embedded_output_file_name(package, license)),
+            )?;
+        }
+    }
+    Ok(())
+}
+
 fn add_included_licenses_to_output_folder(
     file_io: &impl FileIO,
     output_folder: &Path,
@@ -66,7 +90,8 @@ fn add_included_licenses_to_output_folder(
 mod tests {
     use crate::cargo_metadata::Package;
     use crate::file_io::{DirEntry, FileIOSpy};
-    use crate::licenses::copy::copy_licenses;
+    use crate::licenses::copy::{copy_embedded_licenses, copy_licenses};
+    use crate::licenses::embedded::EmbeddedLicenses;
     use std::collections::HashMap;
     use std::ffi::OsString;
     use std::path::PathBuf;
@@ -228,4 +253,32 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn embedded_licenses_are_copied_under_distinguishing_name() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.copy_file.returns.set([Ok(())]);
+
+        let embedded_licenses = EmbeddedLicenses(HashMap::from([(
+            Package::called("zstd_sys"),
+            vec![DirEntry {
+                name: OsString::from("zstd/LICENSE"),
+                path: PathBuf::from("/src/zstd_sys/zstd/LICENSE"),
+                is_file: true,
+            }],
+        )]));
+
+        assert!(
+            copy_embedded_licenses(&file_io_spy, &embedded_licenses, &PathBuf::from("licenses"))
+                .is_ok()
+        );
+
+        assert_eq!(
+            vec![(
+                PathBuf::from("/src/zstd_sys/zstd/LICENSE"),
+                PathBuf::from("licenses/zstd_sys-embedded-zstd-LICENSE")
+            )],
+            file_io_spy.copy_file.arguments.take()
+        );
+    }
 }
@@ -0,0 +1,232 @@
+use crate::cargo_metadata::Package;
+use crate::file_io::{DirEntry, FileIO};
+use crate::licenses::markup::plain_text;
+use crate::licenses::placeholders::placeholders_in;
+use itertools::Itertools;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct Copyright {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub statements: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+}
+
+impl Copyright {
+    pub const fn is_empty(&self) -> bool {
+        self.statements.is_empty() && self.authors.is_empty()
+    }
+}
+
+impl Display for Copyright {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.statements.is_empty() {
+            write!(f, "authors: {}", self.authors.join(", "))
+        } else {
+            write!(f, "{}", self.statements.join("; "))
+        }
+    }
+}
+
+pub fn is_copyright_file(dir_entry: &DirEntry) -> bool {
+    dir_entry.is_file
+        && Path::new(&dir_entry.name)
+            .file_stem()
+            .is_some_and(|stem| stem.eq_ignore_ascii_case("copyright"))
+}
+
+pub fn copyright_statements(text: &str) -> Vec<String> {
+    plain_text(text)
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            is_copyright_notice(&line.to_lowercase()) && placeholders_in(line).is_empty()
+        })
+        .map(ToString::to_string)
+        .unique()
+        .collect()
+}
+
+fn is_copyright_notice(line: &str) -> bool {
+    let has_year = || {
+        line.split(|c: char| !c.is_ascii_digit())
+            .any(|number| number.len() == 4)
+    };
+    if line.starts_with("copyright") {
+        line.contains("(c)") || line.contains('©') || has_year()
+    } else {
+        ["(c)", "©"].iter().any(|symbol| {
+            line.strip_prefix(symbol)
+                .is_some_and(|rest| rest.trim_start().starts_with(|c: char| c.is_ascii_digit()))
+        })
+    }
+}
+
+pub fn find_copyrights(
+    file_io: &impl FileIO,
+    all_licenses: &HashMap<Package, Vec<DirEntry>>,
+) -> anyhow::Result<HashMap<Package, Copyright>> {
+    all_licenses
+        .iter()
+        .map(|(package, licenses)| {
+            Ok((
+                package.clone(),
+                package_copyright(file_io, package, licenses)?,
+            ))
+        })
+        .collect()
+}
+
+pub fn package_copyright(
+    file_io: &impl FileIO,
+    package: &Package,
+    licenses: &[DirEntry],
+) -> anyhow::Result<Copyright> {
+    let copyright_files: Vec<DirEntry> = file_io
+        .read_dir(package.path.as_ref())?
+        .into_iter()
+        .filter(is_copyright_file)
+        .filter(|dir_entry| {
+            !licenses
+                .iter()
+                .any(|license| license.path == dir_entry.path)
+        })
+        .collect();
+    let statements: Vec<String> = licenses
+        .iter()
+        .chain(&copyright_files)
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .map(|dir_entry| file_io.read_file(&dir_entry.path))
+        .collect::<anyhow::Result<Vec<_>>>()?
+        .iter()
+        .flat_map(|contents| copyright_statements(contents))
+        .unique()
+        .collect();
+    let authors = if statements.is_empty() {
+        package.authors.clone()
+    } else {
+        Vec::new()
+    };
+    Ok(Copyright {
+        statements,
+        authors,
+    })
+}
+
+pub fn copyrights_by_name(copyrights: HashMap<Package, Copyright>) -> HashMap<String, Copyright> {
+    let mut by_name: HashMap<String, Copyright> = HashMap::new();
+    for (package, copyright) in copyrights.into_iter().sorted_by(|(a, _), (b, _)| a.cmp(b)) {
+        let merged = by_name.entry(package.normalised_name).or_default();
+        merged.statements = merged
+            .statements
+            .drain(..)
+            .chain(copyright.statements)
+            .unique()
+            .collect();
+        merged.authors = merged
+            .authors
+            .drain(..)
+            .chain(copyright.authors)
+            .unique()
+            .collect();
+    }
+    by_name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::FileIOSpy;
+    use crate::licenses::texts::LICENSE_TEXTS;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    fn entry(name: &str) -> DirEntry {
+        DirEntry {
+            name: OsString::from(name),
+            path: PathBuf::from(name),
+            is_file: true,
+        }
+    }
+
+    #[test]
+    fn copyright_statements_are_extracted() {
+        assert_eq!(
+            vec!["Copyright (c) 2024 Example".to_string()],
+            copyright_statements(
+                "MIT License\n\n  Copyright (c) 2024 Example\n\nPermission is hereby granted"
+            )
+        );
+        assert!(copyright_statements(LICENSE_TEXTS["MIT"]).is_empty());
+        assert!(copyright_statements(LICENSE_TEXTS["Apache-2.0"]).is_empty());
+        assert!(
+            copyright_statements("copyright notice that is included in or attached to the work")
+                .is_empty()
+        );
+        assert_eq!(
+            vec!["Copyright 2018 Example".to_string()],
+            copyright_statements("Copyright 2018 Example")
+        );
+    }
+
+    #[test]
+    fn copyrights_are_found_in_licenses_and_copyright_files() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_dir
+            .returns
+            .set([Ok(vec![entry("COPYRIGHT"), entry("LICENSE-MIT")])]);
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "COPYRIGHT" => "Copyright 2018 Other",
+                _ => "Copyright (c) 2024 Example",
+            }
+            .to_string())
+        });
+        let all_licenses = HashMap::from([(
+            Package::called("example"),
+            vec![entry("LICENSE-APACHE"), entry("LICENSE-MIT")],
+        )]);
+
+        assert_eq!(
+            Copyright {
+                statements: vec![
+                    "Copyright 2018 Other".to_string(),
+                    "Copyright (c) 2024 Example".to_string()
+                ],
+                authors: Vec::new(),
+            },
+            find_copyrights(&file_io_spy, &all_licenses).unwrap()[&Package::called("example")]
+        );
+    }
+
+    #[test]
+    fn authors_are_the_copyright_holders_without_statements() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set([Ok(Vec::new())]);
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_: &PathBuf| Ok(LICENSE_TEXTS["MIT"].to_string()));
+        let package = Package {
+            authors: vec!["Example <example@example.com>".to_string()],
+            ..Package::called("example")
+        };
+        let all_licenses = HashMap::from([(package.clone(), vec![entry("LICENSE")])]);
+
+        let copyright = &find_copyrights(&file_io_spy, &all_licenses).unwrap()[&package];
+
+        assert_eq!(
+            vec!["Example <example@example.com>".to_string()],
+            copyright.authors
+        );
+        assert_eq!(
+            "authors: Example <example@example.com>",
+            copyright.to_string()
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use crate::cargo_metadata::Package;
 use crate::config::{CrateConfig, IncludedLicense};
 use crate::file_io::{DirEntry, FileIO};
+use crate::licenses::copy::{embedded_output_file_name, output_file_name};
+use crate::licenses::embedded::EmbeddedLicenses;
 use crate::log::warning;
 use itertools::Itertools;
 use std::collections::{BTreeSet, HashMap};
@@ -50,10 +52,16 @@ pub fn diff_licenses(
     path: &Path,
     crate_configs: &HashMap<String, CrateConfig>,
     found_licenses: HashMap<Package, Vec<DirEntry>>,
+    embedded_licenses: &EmbeddedLicenses,
 ) -> anyhow::Result<LicenseDiff> {
     let current_licenses = set_of_current_licenses(file_io.read_dir(path)?);
     let mut found_licenses = flatten(found_licenses);
     found_licenses.extend(included_licenses(crate_configs));
+    found_licenses.extend(embedded_licenses.0.iter().flat_map(|(package, licenses)| {
+        licenses
+            .iter()
+            .map(|license| embedded_output_file_name(package, license))
+    }));
 
     Ok(LicenseDiff {
         additional: current_licenses
@@ -79,13 +87,9 @@ fn flatten(found_licenses: HashMap<Package, Vec<DirEntry>>) -> BTreeSet<String>
     found_licenses
         .into_iter()
         .flat_map(|(package, dir_entries)| {
-            dir_entries.into_iter().map(move |dir_entry| {
-                format!(
-                    "{}-{}",
-                    package.normalised_name,
-                    dir_entry.name.to_string_lossy()
-                )
-            })
+            dir_entries
+                .into_iter()
+                .map(move |dir_entry| output_file_name(&package, &dir_entry))
         })
         .collect()
 }
@@ -112,6 +116,7 @@ mod tests {
     use crate::cargo_metadata::Package;
     use crate::config::{CrateConfig, IncludedLicense};
     use crate::file_io::{DirEntry, FileIOSpy};
+    use crate::licenses::embedded::EmbeddedLicenses;
     use std::collections::{BTreeSet, HashMap};
     use std::ffi::OsString;
     use std::path::PathBuf;
@@ -129,7 +134,8 @@ mod tests {
                 &file_io_spy,
                 &PathBuf::new(),
                 &HashMap::new(),
-                HashMap::new()
+                HashMap::new(),
+                &EmbeddedLicenses(HashMap::new())
             )
             .is_err()
         );
@@ -162,7 +168,8 @@ mod tests {
                 &file_io_spy,
                 &PathBuf::new(),
                 &HashMap::new(),
-                found_licenses
+                found_licenses,
+                &EmbeddedLicenses(HashMap::new())
             )
             .unwrap()
             .is_empty()
@@ -201,7 +208,8 @@ mod tests {
                 &file_io_spy,
                 &PathBuf::new(),
                 &HashMap::new(),
-                found_licenses
+                found_licenses,
+                &EmbeddedLicenses(HashMap::new())
             )
             .unwrap()
             .is_empty()
@@ -234,7 +242,8 @@ mod tests {
                 &file_io_spy,
                 &PathBuf::new(),
                 &HashMap::new(),
-                found_licenses
+                found_licenses,
+                &EmbeddedLicenses(HashMap::new())
             )
             .unwrap()
         );
@@ -262,7 +271,8 @@ mod tests {
                 &file_io_spy,
                 &PathBuf::new(),
                 &HashMap::new(),
-                found_licenses
+                found_licenses,
+                &EmbeddedLicenses(HashMap::new())
             )
             .unwrap()
         );
@@ -288,6 +298,7 @@ mod tests {
                     name: "LICENSE".to_string(),
                     text: "I got included!".to_string(),
                 }],
+                ..CrateConfig::default()
             },
         ))
         .collect();
@@ -298,7 +309,8 @@ mod tests {
                 &file_io_spy,
                 &PathBuf::new(),
                 &HashMap::new(),
-                found_licenses.clone()
+                found_licenses.clone(),
+                &EmbeddedLicenses(HashMap::new())
             )
             .unwrap()
             .is_empty()
@@ -312,9 +324,15 @@ mod tests {
 
         // no diff if the additional license is from the included section of the config
         assert!(
-            diff_licenses(&file_io_spy, &PathBuf::new(), &config, found_licenses)
-                .unwrap()
-                .is_empty()
+            diff_licenses(
+                &file_io_spy,
+                &PathBuf::new(),
+                &config,
+                found_licenses,
+                &EmbeddedLicenses(HashMap::new())
+            )
+            .unwrap()
+            .is_empty()
         );
     }
 
@@ -440,10 +458,45 @@ mod tests {
                 &file_io_spy,
                 &PathBuf::new(),
                 &HashMap::new(),
-                found_licenses
+                found_licenses,
+                &EmbeddedLicenses(HashMap::new())
             )
             .unwrap()
             .is_empty()
         );
     }
+
+    #[test]
+    fn embedded_licenses_are_expected_in_output_folder() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set([Ok(vec![DirEntry {
+            name: OsString::from("example-LICENSE"),
+            path: PathBuf::new(),
+            is_file: true,
+        }])]);
+
+        let embedded_licenses = EmbeddedLicenses(HashMap::from([(
+            Package::called("example"),
+            vec![DirEntry {
+                name: OsString::from("vendor/LICENSE"),
+                path: PathBuf::from("example/vendor/LICENSE"),
+                is_file: true,
+            }],
+        )]));
+
+        assert_eq!(
+            LicenseDiff {
+                additional: BTreeSet::from(["example-LICENSE".to_string()]),
+                missing: BTreeSet::from(["example-embedded-vendor-LICENSE".to_string()]),
+            },
+            diff_licenses(
+                &file_io_spy,
+                &PathBuf::new(),
+                &HashMap::new(),
+                HashMap::new(),
+                &embedded_licenses
+            )
+            .unwrap()
+        );
+    }
 }
@@ -0,0 +1,286 @@
+use crate::cargo_metadata::Package;
+use crate::config::CrateConfig;
+use crate::file_io::{DirEntry, FileIO};
+use crate::licenses::collect::{is_skipped_file, skipped_files_for_package};
+use crate::licenses::content::is_text_file;
+use crate::licenses::headers::IGNORED_DIRECTORIES;
+use crate::licenses::is_license::{is_license, is_license_directory};
+use crate::licenses::status::serialize_crate_files;
+use crate::log::note;
+use colored::Colorize;
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fmt::{Display, Formatter};
+
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct EmbeddedLicenses(pub HashMap<Package, Vec<DirEntry>>);
+
+impl EmbeddedLicenses {
+    pub fn any(&self) -> bool {
+        self.0.values().any(|licenses| !licenses.is_empty())
+    }
+}
+
+impl Serialize for EmbeddedLicenses {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_crate_files(
+            &self.0,
+            |license| license.name.to_string_lossy().to_string(),
+            serializer,
+        )
+    }
+}
+
+impl Display for EmbeddedLicenses {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.any() {
+            return Ok(());
+        }
+        writeln!(
+            f,
+            "{}",
+            note(&format!(
+                "{} - found license files of vendored code in subdirectories of:",
+                "embedded third-party licenses".bold()
+            ))
+        )?;
+        for (package, licenses) in self
+            .0
+            .iter()
+            .filter(|(_, licenses)| !licenses.is_empty())
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        {
+            writeln!(
+                f,
+                "\t{} - {}",
+                package.normalised_name.bold(),
+                licenses
+                    .iter()
+                    .map(|license| license.name.to_string_lossy())
+                    .sorted()
+                    .join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn find_embedded_licenses(
+    file_io: &impl FileIO,
+    packages: &[Package],
+    crate_configs: &HashMap<String, CrateConfig>,
+) -> anyhow::Result<EmbeddedLicenses> {
+    packages
+        .iter()
+        .map(|package| {
+            let skipped_files = skipped_files_for_package(package, crate_configs);
+            let mut licenses = Vec::new();
+            for dir_entry in file_io.read_dir(package.path.as_ref())? {
+                if is_subdirectory_to_search(&dir_entry, skipped_files)
+                    && !is_license_directory(&dir_entry)
+                {
+                    licenses.extend(nested_licenses(file_io, &dir_entry, skipped_files));
+                }
+            }
+            licenses.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok((package.clone(), licenses))
+        })
+        .collect::<anyhow::Result<_>>()
+        .map(EmbeddedLicenses)
+}
+
+fn nested_licenses(
+    file_io: &impl FileIO,
+    directory: &DirEntry,
+    skipped_files: &[String],
+) -> Vec<DirEntry> {
+    let Ok(entries) = file_io.read_dir(&directory.path) else {
+        return Vec::new();
+    };
+    let mut licenses = Vec::new();
+    for dir_entry in entries {
+        let dir_entry = DirEntry {
+            name: OsString::from(format!(
+                "{}/{}",
+                directory.name.to_string_lossy(),
+                dir_entry.name.to_string_lossy()
+            )),
+            ..dir_entry
+        };
+        if is_subdirectory_to_search(&dir_entry, skipped_files) {
+            licenses.extend(nested_licenses(file_io, &dir_entry, skipped_files));
+        } else if is_embedded_license(&dir_entry, skipped_files) {
+            licenses.push(dir_entry);
+        }
+    }
+    licenses
+}
+
+fn is_subdirectory_to_search(dir_entry: &DirEntry, skipped_files: &[String]) -> bool {
+    !dir_entry.is_file
+        && !dir_entry.is_linked_directory()
+        && !is_skipped_file(dir_entry, skipped_files)
+        && !dir_entry
+            .path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| IGNORED_DIRECTORIES.contains(&name))
+}
+
+fn is_embedded_license(dir_entry: &DirEntry, skipped_files: &[String]) -> bool {
+    is_license(dir_entry)
+        && is_text_file(&dir_entry.path)
+        && !is_skipped_file(dir_entry, skipped_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::{FileIOSpy, FileSystem};
+    use cargo_metadata::camino::Utf8PathBuf;
+    use std::path::PathBuf;
+
+    fn entry(path: &str, is_file: bool) -> DirEntry {
+        let path = PathBuf::from(path);
+        DirEntry {
+            name: path.file_name().unwrap().to_os_string(),
+            path,
+            is_file,
+        }
+    }
+
+    #[test]
+    fn license_files_in_subdirectories_are_embedded() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "vendor" => vec![entry("vendor/zstd", false)],
+                "vendor/zstd" => vec![
+                    entry("vendor/zstd/LICENSE", true),
+                    entry("vendor/zstd/license.c", true),
+                    entry("vendor/zstd/zstd.c", true),
+                ],
+                "LICENSES" => vec![entry("LICENSES/MIT.txt", true)],
+                "target" => vec![entry("target/LICENSE", true)],
+                _ => vec![
+                    entry("LICENSE", true),
+                    entry("LICENSES", false),
+                    entry("target", false),
+                    entry("vendor", false),
+                ],
+            })
+        });
+
+        let embedded =
+            find_embedded_licenses(&file_io_spy, &[Package::called("example")], &HashMap::new())
+                .unwrap();
+
+        assert_eq!(
+            vec![OsString::from("vendor/zstd/LICENSE")],
+            embedded.0[&Package::called("example")]
+                .iter()
+                .map(|license| license.name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn unreadable_subdirectories_are_ignored() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_dir
+            .returns
+            .set_fn(|path: &PathBuf| match path.to_str().unwrap() {
+                "private" => Err(anyhow::anyhow!("deliberate test error")),
+                "vendor" => Ok(vec![entry("vendor/LICENSE", true)]),
+                _ => Ok(vec![entry("private", false), entry("vendor", false)]),
+            });
+
+        let embedded =
+            find_embedded_licenses(&file_io_spy, &[Package::called("example")], &HashMap::new())
+                .unwrap();
+
+        assert_eq!(
+            vec![OsString::from("vendor/LICENSE")],
+            embedded.0[&Package::called("example")]
+                .iter()
+                .map(|license| license.name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symbolic_links_to_directories_are_not_followed() {
+        let crate_dir = tempfile::tempdir().unwrap();
+        let vendor = crate_dir.path().join("vendor");
+        std::fs::create_dir(&vendor).unwrap();
+        std::fs::write(vendor.join("LICENSE"), "MIT License").unwrap();
+        std::os::unix::fs::symlink("..", vendor.join("loop")).unwrap();
+        let package = Package {
+            path: Utf8PathBuf::try_from(crate_dir.path().to_path_buf()).unwrap(),
+            ..Package::called("example")
+        };
+
+        let embedded = find_embedded_licenses(
+            &FileSystem::default(),
+            std::slice::from_ref(&package),
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![OsString::from("vendor/LICENSE")],
+            embedded.0[&package]
+                .iter()
+                .map(|license| license.name.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn skipped_embedded_licenses_are_not_found() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "vendor" => vec![entry("vendor/COPYING", true)],
+                _ => vec![entry("vendor", false)],
+            })
+        });
+        let crate_configs = HashMap::from([(
+            "example".to_string(),
+            CrateConfig {
+                skip: vec!["vendor/COPYING".to_string()],
+                ..CrateConfig::default()
+            },
+        )]);
+
+        let embedded =
+            find_embedded_licenses(&file_io_spy, &[Package::called("example")], &crate_configs)
+                .unwrap();
+
+        assert!(!embedded.any());
+    }
+
+    #[test]
+    fn display_embedded_licenses() {
+        let embedded = EmbeddedLicenses(HashMap::from([
+            (
+                Package::called("example"),
+                vec![DirEntry {
+                    name: OsString::from("vendor/zstd/LICENSE"),
+                    path: PathBuf::from("vendor/zstd/LICENSE"),
+                    is_file: true,
+                }],
+            ),
+            (Package::called("other"), Vec::new()),
+        ]));
+
+        let display = strip_ansi_escapes::strip_str(embedded.to_string());
+        assert!(display.starts_with("note: embedded third-party licenses"));
+        assert!(display.contains("example - vendor/zstd/LICENSE"));
+        assert!(!display.contains("other"));
+    }
+}
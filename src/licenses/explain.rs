@@ -0,0 +1,466 @@
+use crate::cargo_metadata::Package;
+use crate::config::CrateConfig;
+use crate::file_io::{DirEntry, FileIO};
+use crate::licenses::License;
+use crate::licenses::check::license_status_after_allowed;
+use crate::licenses::collect::{Collected, Discovery, package_files};
+use crate::licenses::headers::{SourceLicenses, scan_source_licenses, with_source_licenses};
+use crate::licenses::siblings::{sibling_version, with_sibling_version};
+use crate::licenses::status::LicenseStatus;
+use crate::licenses::texts::{Identified, LicenseTexts};
+use crate::licenses::validate::{ConfidenceThreshold, declared_match_scores, validate_licenses};
+use colored::Colorize;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+pub struct Explanation {
+    package: Package,
+    confidence_threshold: ConfidenceThreshold,
+    files: Vec<(String, FileDecision)>,
+    source_licenses: Option<SourceLicenses>,
+    raw_status: LicenseStatus,
+    status: LicenseStatus,
+    config_entries: Vec<String>,
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq))]
+enum FileDecision {
+    Accepted(Result<Vec<(String, f32)>, String>),
+    Detected(Identified, Result<Vec<(String, f32)>, String>),
+    Inherited(Result<Vec<(String, f32)>, String>),
+    Sibling(String, Result<Vec<(String, f32)>, String>),
+    Skipped,
+    Notice,
+    NotAFile,
+    NotALicenseName,
+}
+
+pub fn explain_package(
+    file_io: &impl FileIO,
+    license_texts: &LicenseTexts,
+    confidence_threshold: ConfidenceThreshold,
+    package: &Package,
+    crate_configs: &HashMap<String, CrateConfig>,
+    discovery: &Discovery,
+    scan_headers: bool,
+) -> anyhow::Result<Explanation> {
+    let declared = package.license.as_deref().map(License::parse);
+    let confidence_threshold = confidence_threshold.for_package(package, crate_configs);
+    let scores = |entry: &DirEntry| {
+        declared.as_ref().map_or_else(
+            || Ok(Vec::new()),
+            |declared| {
+                declared_match_scores(file_io, license_texts, declared, entry)
+                    .map_err(|error| format!("{error:#}"))
+            },
+        )
+    };
+
+    let package_files = package_files(file_io, package, crate_configs, discovery)?;
+    let licenses: Vec<DirEntry> = package_files
+        .iter()
+        .filter(|(_, collected)| collected.is_license())
+        .map(|(entry, _)| entry.clone())
+        .collect();
+    let version = sibling_version(package, &licenses).unwrap_or_default();
+    let files = package_files
+        .into_iter()
+        .map(|(entry, collected)| {
+            let name = entry.name.to_string_lossy().to_string();
+            match collected {
+                Collected::License => (name, FileDecision::Accepted(scores(&entry))),
+                Collected::Detected(detected) => {
+                    (name, FileDecision::Detected(detected, scores(&entry)))
+                }
+                Collected::Inherited => (
+                    entry.path.display().to_string(),
+                    FileDecision::Inherited(scores(&entry)),
+                ),
+                Collected::SiblingVersion => (
+                    entry.path.display().to_string(),
+                    FileDecision::Sibling(version.clone(), scores(&entry)),
+                ),
+                Collected::Skipped => (name, FileDecision::Skipped),
+                Collected::Notice => (name, FileDecision::Notice),
+                Collected::NotAFile => (name, FileDecision::NotAFile),
+                Collected::NotALicenseName => (name, FileDecision::NotALicenseName),
+            }
+        })
+        .collect();
+
+    let source_licenses = scan_headers.then(|| scan_source_licenses(file_io, package));
+    let mut raw_status = with_sibling_version(
+        validate_licenses(
+            file_io,
+            license_texts,
+            confidence_threshold,
+            declared.as_ref(),
+            &licenses,
+        ),
+        package,
+        &licenses,
+    );
+    if let Some(source_licenses) = &source_licenses {
+        raw_status = with_source_licenses(raw_status, declared.as_ref(), source_licenses);
+    }
+    let status = license_status_after_allowed(raw_status.clone(), package, crate_configs);
+
+    Ok(Explanation {
+        package: package.clone(),
+        confidence_threshold,
+        files,
+        source_licenses,
+        config_entries: config_entries(package, crate_configs, &raw_status, &status),
+        raw_status,
+        status,
+    })
+}
+
+fn config_entries(
+    package: &Package,
+    crate_configs: &HashMap<String, CrateConfig>,
+    raw_status: &LicenseStatus,
+    status: &LicenseStatus,
+) -> Vec<String> {
+    let Some(config) = crate_configs.get(&package.normalised_name) else {
+        return Vec::new();
+    };
+
+    let mut entries: Vec<String> = config
+        .skip
+        .iter()
+        .map(|skipped| format!("skip {skipped}"))
+        .collect();
+    if let Some(threshold) = config.confidence_threshold {
+        entries.push(format!("confidence-threshold {threshold}"));
+    }
+    if let Some(allowed) = &config.allow {
+        if raw_status == status {
+            entries.push(format!("allow {} (not applied)", allowed.name()));
+        } else {
+            entries.push(format!("allow {} (applied)", allowed.name()));
+        }
+    }
+    entries
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} - {}",
+            self.package.normalised_name.bold(),
+            self.package.path
+        )?;
+        writeln!(
+            f,
+            "declared: {}",
+            self.package.license.as_deref().unwrap_or("none")
+        )?;
+        writeln!(f, "confidence threshold: {}", self.confidence_threshold)?;
+
+        writeln!(f, "files:")?;
+        for (name, decision) in &self.files {
+            write!(f, "\t{}", name.bold())?;
+            match decision {
+                FileDecision::Accepted(scores) => {
+                    write!(f, " - accepted")?;
+                    Self::display_scores(f, scores)?;
+                }
+                FileDecision::Detected(detected, scores) => {
+                    write!(
+                        f,
+                        " - accepted, content detected as {} {:.2}",
+                        detected.id, detected.score
+                    )?;
+                    Self::display_scores(f, scores)?;
+                }
+                FileDecision::Inherited(scores) => {
+                    write!(
+                        f,
+                        " - accepted, inherited from the repository or workspace root"
+                    )?;
+                    Self::display_scores(f, scores)?;
+                }
+                FileDecision::Sibling(version, scores) => {
+                    write!(f, " - accepted, from cached version {version} of the crate")?;
+                    Self::display_scores(f, scores)?;
+                }
+                FileDecision::Skipped => writeln!(f, " - skipped by config")?,
+                FileDecision::Notice => {
+                    writeln!(
+                        f,
+                        " - rejected, NOTICE file collected alongside the licenses"
+                    )?;
+                }
+                FileDecision::NotAFile => writeln!(f, " - rejected, not a file")?,
+                FileDecision::NotALicenseName => {
+                    writeln!(f, " - rejected, name is not a license name")?;
+                }
+            }
+        }
+
+        if let Some(source_licenses) = &self.source_licenses {
+            writeln!(f, "source licenses:")?;
+            for (expression, files) in source_licenses {
+                writeln!(f, "\t{} - {}", expression.bold(), files.iter().join(", "))?;
+            }
+        }
+
+        write!(f, "status: {}", self.raw_status.name())?;
+        match self.raw_status.details() {
+            Some(details) => writeln!(f, " - {details}")?,
+            None => writeln!(f)?,
+        }
+        if let LicenseStatus::Modified(files) = &self.raw_status {
+            for file in files {
+                writeln!(f, "\t{} differs from the reference text:", file.name.bold())?;
+                for difference in &file.differences {
+                    writeln!(f, "\t\t{difference}")?;
+                }
+            }
+        }
+        if self.raw_status != self.status {
+            writeln!(f, "allowed by config: {}", self.status.name())?;
+        }
+
+        if !self.config_entries.is_empty() {
+            writeln!(f, "config:")?;
+            for entry in &self.config_entries {
+                writeln!(f, "\t{entry}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Explanation {
+    fn display_scores(
+        f: &mut Formatter<'_>,
+        scores: &Result<Vec<(String, f32)>, String>,
+    ) -> std::fmt::Result {
+        match scores {
+            Ok(scores) => {
+                writeln!(f)?;
+                for (id, score) in scores {
+                    writeln!(f, "\t\t{id} - {score:.2}")?;
+                }
+                Ok(())
+            }
+            Err(error) => writeln!(f, ", but could not be read: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::FileIOSpy;
+    use crate::licenses::content::ContentScan;
+    use crate::licenses::texts::LICENSE_TEXTS;
+    use cargo_metadata::camino::Utf8PathBuf;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    fn package() -> Package {
+        Package {
+            normalised_name: "example".to_string(),
+            path: Utf8PathBuf::from("example"),
+            url: None,
+            license: Some("MIT".to_string()),
+            authors: Vec::new(),
+            source: None,
+        }
+    }
+
+    fn entry(name: &str, is_file: bool) -> DirEntry {
+        DirEntry {
+            name: OsString::from(name),
+            path: PathBuf::from(name),
+            is_file,
+        }
+    }
+
+    fn license_text(id: &str) -> String {
+        LICENSE_TEXTS[id].replace("<year> <copyright holders>", "2024 Example")
+    }
+
+    #[test]
+    fn failure_to_read_dir_causes_error() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_dir
+            .returns
+            .set([Err(anyhow::anyhow!("deliberate test error"))]);
+
+        assert!(
+            explain_package(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                &package(),
+                &HashMap::new(),
+                &Discovery::default(),
+                false
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn explains_why_each_file_was_considered() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set([Ok(vec![
+            entry("src", false),
+            entry("README.md", true),
+            entry("COPYING", true),
+            entry("LICENSE", true),
+            entry("NOTICE", true),
+        ])]);
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_: &PathBuf| Ok(license_text("MIT")));
+        let crate_configs = HashMap::from([(
+            "example".to_string(),
+            CrateConfig {
+                skip: vec!["COPYING".to_string()],
+                ..CrateConfig::default()
+            },
+        )]);
+
+        let explanation = explain_package(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &package(),
+            &crate_configs,
+            &Discovery::default(),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                ("COPYING".to_string(), FileDecision::Skipped),
+                (
+                    "LICENSE".to_string(),
+                    FileDecision::Accepted(Ok(vec![("MIT".to_string(), 1.0)]))
+                ),
+                ("NOTICE".to_string(), FileDecision::Notice),
+                ("README.md".to_string(), FileDecision::NotALicenseName),
+                ("src".to_string(), FileDecision::NotAFile),
+            ],
+            explanation.files
+        );
+        assert_eq!(LicenseStatus::Valid, explanation.status);
+        assert_eq!(vec!["skip COPYING".to_string()], explanation.config_entries);
+    }
+
+    #[test]
+    fn explains_status_allowed_by_config() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_dir
+            .returns
+            .set([Ok(vec![entry("LICENSE", true)])]);
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_: &PathBuf| Ok("not a license".to_string()));
+        let crate_configs = HashMap::from([(
+            "example".to_string(),
+            CrateConfig {
+                allow: Some(LicenseStatus::Mismatch(vec!["LICENSE".into()])),
+                ..CrateConfig::default()
+            },
+        )]);
+
+        let explanation = strip_ansi_escapes::strip_str(
+            explain_package(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                &package(),
+                &crate_configs,
+                &Discovery::default(),
+                false,
+            )
+            .unwrap()
+            .to_string(),
+        );
+
+        assert!(explanation.contains("status: mismatch - LICENSE (score"));
+        assert!(explanation.contains("allowed by config: valid"));
+        assert!(explanation.contains("allow mismatch (applied)"));
+    }
+
+    #[test]
+    fn explains_passages_differing_from_reference_text() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_dir
+            .returns
+            .set([Ok(vec![entry("LICENSE", true)])]);
+        file_io_spy.read_file.returns.set_fn(|_: &PathBuf| {
+            Ok(license_text("MIT").replace(
+                "free of charge,",
+                "free of charge, for non-commercial use only,",
+            ))
+        });
+
+        let explanation = explain_package(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &package(),
+            &HashMap::new(),
+            &Discovery::default(),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!("modified", explanation.status.name());
+        let display = strip_ansi_escapes::strip_str(explanation.to_string());
+        assert!(display.contains("LICENSE differs from the reference text:"));
+        assert!(display.contains("+ for non-commercial use only"));
+    }
+
+    #[test]
+    fn notice_files_are_not_candidates_even_when_scanning_content() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_dir
+            .returns
+            .set([Ok(vec![entry("LICENSE", true), entry("NOTICE", true)])]);
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_: &PathBuf| Ok(license_text("MIT")));
+        let license_texts = LicenseTexts::default();
+
+        let explanation = explain_package(
+            &file_io_spy,
+            &license_texts,
+            ConfidenceThreshold::default(),
+            &package(),
+            &HashMap::new(),
+            &Discovery {
+                content_scan: Some(ContentScan {
+                    license_texts: &license_texts,
+                    confidence_threshold: ConfidenceThreshold::default(),
+                }),
+                ..Discovery::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ("NOTICE".to_string(), FileDecision::Notice),
+            explanation.files[1]
+        );
+        assert_eq!(LicenseStatus::Valid, explanation.status);
+    }
+}
@@ -0,0 +1,259 @@
+use crate::cargo_metadata::Package;
+use crate::file_io::FileIO;
+use crate::licenses::License;
+use crate::licenses::status::LicenseStatus;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+const IDENTIFIER_TAG: &str = "SPDX-License-Identifier:";
+const HEADER_LINES: usize = 20;
+pub const IGNORED_DIRECTORIES: [&str; 2] = [".git", "target"];
+
+pub type SourceLicenses = BTreeMap<String, BTreeSet<String>>;
+
+pub fn scan_source_licenses(file_io: &impl FileIO, package: &Package) -> SourceLicenses {
+    let mut source_licenses = SourceLicenses::new();
+    scan_directory(file_io, package.path.as_ref(), "", &mut source_licenses);
+    source_licenses
+}
+
+fn scan_directory(
+    file_io: &impl FileIO,
+    directory: &Path,
+    prefix: &str,
+    source_licenses: &mut SourceLicenses,
+) {
+    let Ok(entries) = file_io.read_dir(directory) else {
+        return;
+    };
+    for entry in entries {
+        let file_name = entry.name.to_string_lossy();
+        let name = format!("{prefix}{file_name}");
+        if !entry.is_file {
+            if !IGNORED_DIRECTORIES.contains(&file_name.as_ref()) && !entry.is_linked_directory() {
+                scan_directory(file_io, &entry.path, &format!("{name}/"), source_licenses);
+            }
+            continue;
+        }
+        let Ok(contents) = file_io.read_file(&entry.path) else {
+            continue;
+        };
+        let expressions = match name.as_str() {
+            "REUSE.toml" => reuse_toml_expressions(&contents),
+            ".reuse/dep5" => dep5_expressions(&contents),
+            _ => header_expressions(&contents),
+        };
+        for expression in expressions {
+            source_licenses
+                .entry(expression)
+                .or_default()
+                .insert(name.clone());
+        }
+    }
+}
+
+fn header_expressions(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .take(HEADER_LINES)
+        .filter_map(|line| line.split_once(IDENTIFIER_TAG))
+        .map(|(_, expression)| {
+            expression
+                .trim()
+                .trim_end_matches("*/")
+                .trim_end_matches("-->")
+                .trim()
+                .to_string()
+        })
+        .filter(|expression| !expression.is_empty())
+        .collect()
+}
+
+fn reuse_toml_expressions(contents: &str) -> Vec<String> {
+    let Ok(reuse) = toml::from_str::<toml::Table>(contents) else {
+        return Vec::new();
+    };
+    reuse
+        .get("annotations")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|annotation| annotation.get("SPDX-License-Identifier"))
+        .flat_map(|identifier| match identifier {
+            toml::Value::String(expression) => vec![expression.clone()],
+            toml::Value::Array(expressions) => expressions
+                .iter()
+                .filter_map(|expression| expression.as_str().map(ToString::to_string))
+                .collect(),
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+fn dep5_expressions(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .filter_map(|line| line.strip_prefix("License:"))
+        .map(|expression| expression.trim().to_string())
+        .filter(|expression| !expression.is_empty())
+        .collect()
+}
+
+pub fn undeclared_source_licenses(
+    declared: &License,
+    source_licenses: &SourceLicenses,
+) -> Vec<String> {
+    let declared_ids = declared.ids();
+    source_licenses
+        .keys()
+        .filter(|expression| {
+            License::parse(expression)
+                .satisfied_branch(|id| declared_ids.iter().any(|declared_id| declared_id == id))
+                .is_none()
+        })
+        .cloned()
+        .collect()
+}
+
+pub fn with_source_licenses(
+    license_status: LicenseStatus,
+    declared: Option<&License>,
+    source_licenses: &SourceLicenses,
+) -> LicenseStatus {
+    let Some(declared) = declared.filter(|_| license_status.is_valid()) else {
+        return license_status;
+    };
+    let undeclared = undeclared_source_licenses(declared, source_licenses);
+    if undeclared.is_empty() {
+        license_status
+    } else {
+        LicenseStatus::UndeclaredSourceLicense(undeclared)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::{DirEntry, FileIOSpy, FileSystem};
+    use cargo_metadata::camino::Utf8PathBuf;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    fn entry(name: &str, is_file: bool) -> DirEntry {
+        DirEntry {
+            name: OsString::from(name),
+            path: PathBuf::from(name),
+            is_file,
+        }
+    }
+
+    #[test]
+    fn identifiers_are_collected_from_headers_of_nested_files() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set([
+            Ok(vec![entry("lib.rs", true), entry("vendor", false)]),
+            Ok(vec![entry("vendor.c", true)]),
+        ]);
+        file_io_spy.read_file.returns.set([
+            Ok("// SPDX-License-Identifier: MIT OR Apache-2.0\nfn main() {}".to_string()),
+            Ok("/* SPDX-License-Identifier: GPL-3.0-only */\nint main() {}".to_string()),
+        ]);
+
+        assert_eq!(
+            SourceLicenses::from([
+                (
+                    "GPL-3.0-only".to_string(),
+                    BTreeSet::from(["vendor/vendor.c".to_string()])
+                ),
+                (
+                    "MIT OR Apache-2.0".to_string(),
+                    BTreeSet::from(["lib.rs".to_string()])
+                ),
+            ]),
+            scan_source_licenses(&file_io_spy, &Package::called("example"))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symbolic_links_to_directories_are_not_followed() {
+        let crate_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(crate_dir.path().join("src")).unwrap();
+        std::fs::write(
+            crate_dir.path().join("src/lib.rs"),
+            "// SPDX-License-Identifier: MIT\n",
+        )
+        .unwrap();
+        std::os::unix::fs::symlink("..", crate_dir.path().join("src/loop")).unwrap();
+        let package = Package {
+            path: Utf8PathBuf::try_from(crate_dir.path().to_path_buf()).unwrap(),
+            ..Package::called("example")
+        };
+
+        assert_eq!(
+            SourceLicenses::from([(
+                "MIT".to_string(),
+                BTreeSet::from(["src/lib.rs".to_string()])
+            )]),
+            scan_source_licenses(&FileSystem::default(), &package)
+        );
+    }
+
+    #[test]
+    fn identifiers_are_collected_from_reuse_files() {
+        assert_eq!(
+            vec!["MIT".to_string(), "CC0-1.0".to_string()],
+            reuse_toml_expressions(
+                r#"
+                version = 1
+
+                [[annotations]]
+                path = "src/**"
+                SPDX-License-Identifier = "MIT"
+
+                [[annotations]]
+                path = "docs/**"
+                SPDX-License-Identifier = ["CC0-1.0"]"#
+            )
+        );
+        assert_eq!(
+            vec!["Apache-2.0".to_string()],
+            dep5_expressions("Files: *\nCopyright: 2024 Example\nLicense: Apache-2.0\n")
+        );
+    }
+
+    #[test]
+    fn identifiers_after_the_header_are_ignored() {
+        let contents = format!(
+            "{}const TAG: &str = \"SPDX-License-Identifier: GPL-3.0-only\";",
+            "\n".repeat(HEADER_LINES)
+        );
+        assert!(header_expressions(&contents).is_empty());
+    }
+
+    #[test]
+    fn source_licenses_not_covered_by_declared_are_undeclared() {
+        let source_licenses = SourceLicenses::from([
+            ("MIT".to_string(), BTreeSet::new()),
+            ("MIT OR Apache-2.0".to_string(), BTreeSet::new()),
+            ("GPL-3.0-only".to_string(), BTreeSet::new()),
+        ]);
+
+        assert_eq!(
+            LicenseStatus::UndeclaredSourceLicense(vec!["GPL-3.0-only".to_string()]),
+            with_source_licenses(
+                LicenseStatus::Valid,
+                Some(&License::parse("MIT")),
+                &source_licenses
+            )
+        );
+        assert_eq!(
+            LicenseStatus::TooFew,
+            with_source_licenses(
+                LicenseStatus::TooFew,
+                Some(&License::parse("MIT")),
+                &source_licenses
+            )
+        );
+    }
+}
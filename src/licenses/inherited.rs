@@ -0,0 +1,242 @@
+use crate::cargo_metadata::Package;
+use crate::file_io::{DirEntry, FileIO};
+use crate::licenses::collect::is_skipped_file;
+use crate::licenses::is_license::is_license;
+use crate::licenses::status::serialize_crate_files;
+use crate::log::note;
+use colored::Colorize;
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+const REPOSITORY_ROOT_MARKERS: [&str; 3] = [".git", ".cargo-ok", ".cargo-checksum.json"];
+
+const MAX_ROOT_DISTANCE: usize = 6;
+
+pub fn root_licenses(
+    file_io: &impl FileIO,
+    package: &Package,
+    package_entries: &[DirEntry],
+    skipped_files: &[String],
+) -> Vec<DirEntry> {
+    if is_root(file_io, package_entries) {
+        return Vec::new();
+    }
+    let mut licenses = Vec::new();
+    for directory in package.path.ancestors().skip(1).take(MAX_ROOT_DISTANCE) {
+        let Ok(entries) = file_io.read_dir(directory.as_ref()) else {
+            break;
+        };
+        if licenses.is_empty() {
+            licenses = entries
+                .iter()
+                .filter(|dir_entry| {
+                    is_license(dir_entry) && !is_skipped_file(dir_entry, skipped_files)
+                })
+                .sorted_by(|a, b| a.name.cmp(&b.name))
+                .cloned()
+                .collect();
+        }
+        if is_root(file_io, &entries) {
+            return licenses;
+        }
+    }
+    Vec::new()
+}
+
+fn is_root(file_io: &impl FileIO, entries: &[DirEntry]) -> bool {
+    entries.iter().any(|dir_entry| {
+        REPOSITORY_ROOT_MARKERS
+            .iter()
+            .any(|marker| dir_entry.name == *marker)
+            || dir_entry.name == "Cargo.toml" && is_workspace_manifest(file_io, &dir_entry.path)
+    })
+}
+
+fn is_workspace_manifest(file_io: &impl FileIO, path: &Path) -> bool {
+    file_io.read_file(path).is_ok_and(|contents| {
+        contents.lines().any(|line| {
+            let line = line.trim();
+            line == "[workspace]" || line.starts_with("[workspace.")
+        })
+    })
+}
+
+pub fn is_inherited(package: &Package, dir_entry: &DirEntry) -> bool {
+    !dir_entry.path.starts_with(&package.path)
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct InheritedLicenses(pub HashMap<Package, Vec<DirEntry>>);
+
+pub fn find_inherited_licenses(
+    all_licenses: &HashMap<Package, Vec<DirEntry>>,
+) -> InheritedLicenses {
+    InheritedLicenses(
+        all_licenses
+            .iter()
+            .filter_map(|(package, licenses)| {
+                let inherited: Vec<DirEntry> = licenses
+                    .iter()
+                    .filter(|dir_entry| is_inherited(package, dir_entry))
+                    .cloned()
+                    .collect();
+                (!inherited.is_empty()).then(|| (package.clone(), inherited))
+            })
+            .collect(),
+    )
+}
+
+impl Serialize for InheritedLicenses {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_crate_files(
+            &self.0,
+            |license| license.path.display().to_string(),
+            serializer,
+        )
+    }
+}
+
+impl Display for InheritedLicenses {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        writeln!(
+            f,
+            "{}",
+            note(&format!(
+                "{} - found licenses only at the repository or workspace root for:",
+                "inherited".bold()
+            ))
+        )?;
+        for (package, licenses) in self.0.iter().sorted_by(|(a, _), (b, _)| a.cmp(b)) {
+            writeln!(
+                f,
+                "\t{} - {}",
+                package.normalised_name.bold(),
+                licenses
+                    .iter()
+                    .map(|license| license.path.display())
+                    .join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::FileIOSpy;
+    use cargo_metadata::camino::Utf8PathBuf;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    fn entry(directory: &str, name: &str) -> DirEntry {
+        DirEntry {
+            name: OsString::from(name),
+            path: Path::new(directory).join(name),
+            is_file: true,
+        }
+    }
+
+    fn package() -> Package {
+        Package {
+            path: Utf8PathBuf::from("repo/crates/example"),
+            ..Package::called("example")
+        }
+    }
+
+    #[test]
+    fn licenses_are_inherited_from_the_repository_root() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "repo" => vec![entry("repo", ".git"), entry("repo", "LICENSE")],
+                _ => Vec::new(),
+            })
+        });
+
+        assert_eq!(
+            vec![entry("repo", "LICENSE")],
+            root_licenses(&file_io_spy, &package(), &[], &[])
+        );
+        assert_eq!(
+            vec![PathBuf::from("repo/crates"), PathBuf::from("repo")],
+            file_io_spy.read_dir.arguments.take()
+        );
+    }
+
+    #[test]
+    fn licenses_are_not_inherited_without_a_root_above_the_crate() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "repo" => vec![entry("repo", "LICENSE")],
+                _ => Vec::new(),
+            })
+        });
+
+        assert!(root_licenses(&file_io_spy, &package(), &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn licenses_are_not_inherited_from_a_distant_root() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "home" => vec![entry("home", ".git"), entry("home", "LICENSE")],
+                _ => Vec::new(),
+            })
+        });
+        let package = Package {
+            path: Utf8PathBuf::from("home/a/b/c/d/e/f/example"),
+            ..Package::called("example")
+        };
+
+        assert!(root_licenses(&file_io_spy, &package, &[], &[]).is_empty());
+        assert_eq!(
+            MAX_ROOT_DISTANCE,
+            file_io_spy.read_dir.arguments.take().len()
+        );
+    }
+
+    #[test]
+    fn licenses_are_not_inherited_from_above_the_workspace_root() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "repo/crates" => vec![entry("repo/crates", "Cargo.toml")],
+                "repo" => vec![entry("repo", "LICENSE")],
+                _ => Vec::new(),
+            })
+        });
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_| Ok("[workspace]\nmembers = [\"example\"]".to_string()));
+
+        assert!(root_licenses(&file_io_spy, &package(), &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn licenses_outside_the_crate_are_reported_as_inherited() {
+        let all_licenses = HashMap::from([
+            (package(), vec![entry("repo", "LICENSE")]),
+            (Package::called("another"), vec![entry("", "LICENSE")]),
+        ]);
+
+        let inherited = find_inherited_licenses(&all_licenses);
+
+        assert_eq!(
+            InheritedLicenses(HashMap::from([(package(), vec![entry("repo", "LICENSE")])])),
+            inherited
+        );
+        let display = strip_ansi_escapes::strip_str(inherited.to_string());
+        assert!(display.starts_with("note: inherited"));
+        assert!(display.contains("example - repo/LICENSE"));
+    }
+}
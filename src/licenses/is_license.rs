@@ -1,12 +1,22 @@
 use crate::file_io::DirEntry;
+use std::ffi::OsStr;
+use std::path::Path;
 use strsim::levenshtein;
 
 pub fn is_license(dir_entry: &DirEntry) -> bool {
-    if !dir_entry.is_file {
-        return false;
-    }
+    dir_entry.is_file && is_license_name(&dir_entry.name)
+}
 
-    let filename = dir_entry.name.to_string_lossy().to_lowercase();
+pub fn is_license_directory(dir_entry: &DirEntry) -> bool {
+    !dir_entry.is_file && is_license_name(&dir_entry.name)
+}
+
+fn is_license_name(name: &OsStr) -> bool {
+    let filename = Path::new(name)
+        .file_name()
+        .unwrap_or(name)
+        .to_string_lossy()
+        .to_lowercase();
 
     let candidates = ["license", "copying", "copyright"];
 
@@ -25,7 +35,7 @@ pub fn is_license(dir_entry: &DirEntry) -> bool {
 #[cfg(test)]
 mod tests {
     use crate::file_io::DirEntry;
-    use crate::licenses::is_license::is_license;
+    use crate::licenses::is_license::{is_license, is_license_directory};
     use std::ffi::OsString;
     use std::path::PathBuf;
 
@@ -79,4 +89,30 @@ mod tests {
             }));
         }
     }
+
+    #[test]
+    fn license_named_directories_are_license_directories() {
+        for directory in ["LICENSES", "license", "licence"] {
+            assert!(is_license_directory(&DirEntry {
+                name: OsString::from(directory),
+                path: PathBuf::new(),
+                is_file: false
+            }));
+        }
+        assert!(!is_license(&DirEntry {
+            name: OsString::from("licenses/MIT"),
+            path: PathBuf::new(),
+            is_file: true
+        }));
+        assert!(!is_license_directory(&DirEntry {
+            name: OsString::from("src"),
+            path: PathBuf::new(),
+            is_file: false
+        }));
+        assert!(!is_license_directory(&DirEntry {
+            name: OsString::from("LICENSE"),
+            path: PathBuf::new(),
+            is_file: true
+        }));
+    }
 }
@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+
+const COMMENT_LEADERS: [&str; 7] = ["///", "//!", "//", "#", "--", ";", "*"];
+const COMMENT_DELIMITERS: [&str; 4] = ["/*", "/**", "*/", "**/"];
+const HTML_ENTITIES: [(&str, &str); 6] = [
+    ("&nbsp;", " "),
+    ("&lt;", "<"),
+    ("&gt;", ">"),
+    ("&quot;", "\""),
+    ("&#39;", "'"),
+    ("&amp;", "&"),
+];
+
+pub fn plain_text(contents: &str) -> String {
+    let contents = if is_html(contents) {
+        Cow::Owned(strip_html(contents))
+    } else {
+        Cow::Borrowed(contents)
+    };
+    let leader = comment_leader(&contents);
+    contents
+        .lines()
+        .filter(|line| leader.is_none() || !COMMENT_DELIMITERS.contains(&line.trim()))
+        .map(|line| {
+            let line = leader.map_or(line, |leader| {
+                line.trim_start()
+                    .strip_prefix(leader)
+                    .unwrap_or_else(|| line.trim_start())
+            });
+            strip_markdown(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn is_html(contents: &str) -> bool {
+    let lowercase = contents.to_lowercase();
+    ["<html", "<body", "<p>", "<br", "<div"]
+        .iter()
+        .any(|tag| lowercase.contains(tag))
+}
+
+const HIDDEN_ELEMENTS: [&str; 3] = ["head", "style", "script"];
+const BLOCK_ELEMENTS: [&str; 12] = [
+    "p", "br", "div", "li", "pre", "h1", "h2", "h3", "h4", "h5", "h6", "tr",
+];
+
+fn strip_html(contents: &str) -> String {
+    let mut text = String::with_capacity(contents.len());
+    let mut hidden: Option<String> = None;
+    let mut rest = contents;
+    while let Some(start) = rest.find('<') {
+        if hidden.is_none() {
+            text.push_str(&rest[..start]);
+        }
+        let Some(end) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = rest[start + 1..start + end].to_lowercase();
+        rest = &rest[start + end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(char::is_ascii_alphanumeric)
+            .collect();
+        match &hidden {
+            Some(element) if closing && *element == name => hidden = None,
+            None if !closing && HIDDEN_ELEMENTS.contains(&name.as_str()) => hidden = Some(name),
+            None if BLOCK_ELEMENTS.contains(&name.as_str()) => text.push('\n'),
+            _ => {}
+        }
+    }
+    if hidden.is_none() {
+        text.push_str(rest);
+    }
+    HTML_ENTITIES
+        .iter()
+        .fold(text, |text, (entity, replacement)| {
+            text.replace(entity, replacement)
+        })
+}
+
+fn comment_leader(contents: &str) -> Option<&'static str> {
+    let lines: Vec<&str> = contents
+        .lines()
+        .map(str::trim_start)
+        .filter(|line| !line.is_empty() && !COMMENT_DELIMITERS.contains(&line.trim_end()))
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+    COMMENT_LEADERS
+        .iter()
+        .find(|leader| lines.iter().all(|line| line.starts_with(*leader)))
+        .copied()
+}
+
+fn strip_markdown(line: &str) -> String {
+    let trimmed = line.trim();
+    if trimmed.len() >= 3
+        && ['-', '*', '_', '=']
+            .iter()
+            .any(|rule| trimmed.chars().all(|c| c == *rule || c == ' '))
+    {
+        return String::new();
+    }
+    let line = trimmed
+        .trim_start_matches('#')
+        .trim_start_matches('>')
+        .trim_start();
+    let line = strip_links(line);
+    line.replace("**", "").replace("__", "").replace('`', "")
+}
+
+fn strip_links(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("](") {
+        let Some(end) = rest[start..].find(')') else {
+            break;
+        };
+        let before = &rest[..start];
+        text.push_str(&before.replacen('[', "", 1));
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_text_is_unchanged() {
+        assert_eq!(
+            "Permission is hereby granted, free of charge,\nto any person",
+            plain_text("Permission is hereby granted, free of charge,\nto any person")
+        );
+    }
+
+    #[test]
+    fn markdown_is_stripped() {
+        assert_eq!(
+            "MIT License\n\n\nPermission is not granted\nsee https://opensource.org",
+            plain_text(
+                "# MIT License\n\n---\nPermission is **not** granted\nsee [https://opensource.org](https://opensource.org)"
+            )
+        );
+    }
+
+    #[test]
+    fn html_is_stripped() {
+        assert_eq!(
+            "MIT License\n\n\nPermission & more",
+            plain_text(
+                "<html><head><title>MIT</title></head><body><h1>MIT License</h1>\n<p>Permission &amp; more</p></body></html>"
+            )
+            .trim()
+        );
+    }
+
+    #[test]
+    fn comment_leaders_are_stripped() {
+        assert_eq!(
+            "MIT License\n\nPermission is granted",
+            plain_text("/*\n * MIT License\n *\n * Permission is granted\n */")
+        );
+        assert_eq!(
+            "MIT License\nPermission is granted",
+            plain_text("// MIT License\n// Permission is granted")
+        );
+    }
+}
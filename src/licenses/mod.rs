@@ -1,18 +1,33 @@
 pub mod check;
 pub mod collect;
+pub mod content;
 pub mod copy;
+pub mod copyright;
 pub mod diff;
+pub mod embedded;
+pub mod explain;
+pub mod headers;
+pub mod inherited;
 pub mod is_license;
+pub mod markup;
+pub mod modified;
+pub mod notice_files;
+mod notices;
+pub mod placeholders;
+pub mod prefer;
+pub mod siblings;
 pub mod status;
 pub mod subcommand;
 pub mod summarise;
+pub mod texts;
 pub mod unused;
 pub mod validate;
 
 use itertools::Itertools;
 use serde::{Serialize, Serializer};
-use spdx::expression::ExpressionReq;
-use spdx::{Expression, LicenseReq, ParseMode};
+use spdx::expression::{ExprNode, ExpressionReq, Operator};
+use spdx::{Expression, LicenseItem, LicenseReq, ParseMode};
+use std::cmp::Ordering;
 use std::fmt::Display;
 use std::hash::{Hash, Hasher};
 
@@ -41,6 +56,101 @@ impl License {
             Self::Unknown(_) => itertools::Either::Right(std::iter::empty()),
         }
     }
+
+    pub fn ids(&self) -> Vec<String> {
+        match self {
+            Self::Known(_) => self
+                .requirements()
+                .map(|expression| license_id(&expression.req.license))
+                .collect(),
+            Self::Unknown(license) => vec![license.clone()],
+        }
+    }
+
+    pub fn exceptions(&self) -> Vec<(String, String)> {
+        self.requirements()
+            .filter_map(|expression| {
+                let addition = expression.req.addition.as_ref()?;
+                Some((license_id(&expression.req.license), addition.to_string()))
+            })
+            .collect()
+    }
+
+    pub fn satisfied_branch(&self, mut is_found: impl FnMut(&str) -> bool) -> Option<String> {
+        self.fold(
+            |id, requirement| is_found(id).then_some(requirement),
+            |left, right| Some(format!("{} AND {}", left?, right?)),
+            Option::or,
+        )
+    }
+
+    pub fn preferred_branch(&self, preferences: &[String]) -> Self {
+        let unranked = preferences.len();
+        let rank = |id: &str| {
+            preferences
+                .iter()
+                .position(|preference| preference == id)
+                .unwrap_or(unranked)
+        };
+
+        let branch = self.fold(
+            |id, requirement| Branch {
+                rank: rank(id),
+                expression: requirement,
+                alternatives: false,
+            },
+            |left, right| Branch {
+                rank: left.rank.max(right.rank),
+                expression: format!("{} AND {}", left.parenthesised(), right.parenthesised()),
+                alternatives: false,
+            },
+            |left, right| match left.rank.cmp(&right.rank) {
+                Ordering::Less => left,
+                Ordering::Greater => right,
+                Ordering::Equal if left.rank < unranked => left,
+                Ordering::Equal => Branch {
+                    rank: unranked,
+                    expression: format!("{} OR {}", left.expression, right.expression),
+                    alternatives: true,
+                },
+            },
+        );
+
+        Self::parse(&branch.expression)
+    }
+
+    fn fold<T>(
+        &self,
+        mut leaf: impl FnMut(&str, String) -> T,
+        and: impl Fn(T, T) -> T,
+        or: impl Fn(T, T) -> T,
+    ) -> T {
+        let expression = match self {
+            Self::Known(expression) => expression,
+            Self::Unknown(license) => return leaf(license, license.clone()),
+        };
+
+        // expressions are stored in postfix order, so operators combine the two previous results
+        let mut stack = Vec::new();
+        for node in expression.iter() {
+            let result = match node {
+                ExprNode::Req(expression_req) => leaf(
+                    &license_id(&expression_req.req.license),
+                    expression_req.req.to_string(),
+                ),
+                ExprNode::Op(operator) => {
+                    let right = stack.pop().expect("operator without right operand");
+                    let left = stack.pop().expect("operator without left operand");
+                    match operator {
+                        Operator::And => and(left, right),
+                        Operator::Or => or(left, right),
+                    }
+                }
+            };
+            stack.push(result);
+        }
+        stack.pop().expect("expression without requirements")
+    }
 }
 
 impl PartialEq for License {
@@ -92,6 +202,29 @@ impl Display for License {
     }
 }
 
+struct Branch {
+    rank: usize,
+    expression: String,
+    alternatives: bool,
+}
+
+impl Branch {
+    fn parenthesised(&self) -> String {
+        if self.alternatives {
+            format!("({})", self.expression)
+        } else {
+            self.expression.clone()
+        }
+    }
+}
+
+fn license_id(license: &LicenseItem) -> String {
+    match license {
+        LicenseItem::Spdx { id, .. } => id.name.to_string(),
+        LicenseItem::Other(license_ref) => license_ref.to_string(),
+    }
+}
+
 fn sorted_requirements(expression: &Expression) -> Vec<LicenseReq> {
     expression
         .requirements()
@@ -196,6 +329,105 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ids_of_known_license_in_expression_order() {
+        assert_eq!(
+            vec!["MIT", "Apache-2.0", "LicenseRef-Custom"],
+            License::parse("(MIT OR Apache-2.0) AND LicenseRef-Custom").ids()
+        );
+    }
+
+    #[test]
+    fn ids_of_unknown_license_is_the_license() {
+        assert_eq!(
+            vec!["not-a-real-license"],
+            License::parse("not-a-real-license").ids()
+        );
+    }
+
+    #[test]
+    fn exceptions_are_paired_with_their_license() {
+        assert_eq!(
+            vec![("Apache-2.0".to_string(), "LLVM-exception".to_string())],
+            License::parse("MIT OR Apache-2.0 WITH LLVM-exception").exceptions()
+        );
+    }
+
+    #[test]
+    fn or_is_satisfied_by_either_alternative() {
+        assert_eq!(
+            Some("Apache-2.0".to_string()),
+            License::parse("MIT OR Apache-2.0").satisfied_branch(|id| id == "Apache-2.0")
+        );
+    }
+
+    #[test]
+    fn and_is_not_satisfied_by_a_single_requirement() {
+        assert_eq!(
+            None,
+            License::parse("MIT AND Apache-2.0").satisfied_branch(|id| id == "MIT")
+        );
+    }
+
+    #[test]
+    fn nested_expression_reports_satisfied_branch() {
+        assert_eq!(
+            Some("MIT AND Unicode-3.0".to_string()),
+            License::parse("(MIT OR Apache-2.0) AND Unicode-3.0")
+                .satisfied_branch(|id| id != "Apache-2.0")
+        );
+    }
+
+    #[test]
+    fn unknown_license_is_satisfied_by_itself() {
+        assert_eq!(
+            Some("not-a-real-license".to_string()),
+            License::parse("not-a-real-license").satisfied_branch(|_| true)
+        );
+    }
+
+    #[test]
+    fn preferred_alternative_is_chosen() {
+        let preferences = ["MIT".to_string(), "Apache-2.0".to_string()];
+        for license in ["MIT OR Apache-2.0", "Apache-2.0 OR MIT", "Apache-2.0/MIT"] {
+            assert_eq!(
+                "MIT",
+                License::parse(license)
+                    .preferred_branch(&preferences)
+                    .to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn preferred_alternative_is_chosen_within_and() {
+        assert_eq!(
+            "Apache-2.0 AND Unicode-3.0",
+            License::parse("(MIT OR Apache-2.0) AND Unicode-3.0")
+                .preferred_branch(&["Apache-2.0".to_string()])
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn alternatives_without_a_preference_are_kept() {
+        assert_eq!(
+            License::parse("(BSD-3-Clause OR Zlib) AND Unicode-3.0"),
+            License::parse("(BSD-3-Clause OR Zlib) AND Unicode-3.0")
+                .preferred_branch(&["MIT".to_string()])
+        );
+    }
+
+    #[test]
+    fn preferred_branch_keeps_with_clause() {
+        assert_eq!(
+            "Apache-2.0 WITH LLVM-exception",
+            License::parse("Apache-2.0 WITH LLVM-exception OR MIT")
+                .preferred_branch(&["Apache-2.0".to_string()])
+                .to_string()
+        );
+    }
+
     #[test]
     fn display_known_license() {
         assert_eq!("MIT", License::parse("MIT").to_string());
@@ -0,0 +1,195 @@
+use serde::Serialize;
+use spdx::detection::TextData;
+use std::fmt::{Display, Formatter};
+
+pub const VERBATIM_SCORE: f32 = 0.99;
+
+// the common word count of texts within this bound always fits in a u16
+const MAX_COMPARED_WORDS: usize = 4096 * 4096;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difference {
+    Added(String),
+    Removed(String),
+}
+
+impl Display for Difference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added(passage) => write!(f, "+ {passage}"),
+            Self::Removed(passage) => write!(f, "- {passage}"),
+        }
+    }
+}
+
+pub fn differing_passages(text: &TextData, reference: &TextData) -> Vec<Difference> {
+    let words = comparable_words(text);
+    let reference_words = comparable_words(reference);
+
+    let prefix = words
+        .iter()
+        .zip(&reference_words)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = words[prefix..]
+        .iter()
+        .rev()
+        .zip(reference_words[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let words = &words[prefix..words.len() - suffix];
+    let reference_words = &reference_words[prefix..reference_words.len() - suffix];
+
+    if words.len() * reference_words.len() > MAX_COMPARED_WORDS {
+        let mut differences = Vec::new();
+        push_passages(
+            &mut differences,
+            &mut words.iter().map(String::as_str).collect(),
+            &mut reference_words.iter().map(String::as_str).collect(),
+        );
+        return differences;
+    }
+    word_differences(words, reference_words)
+}
+
+pub fn has_additions(differences: &[Difference]) -> bool {
+    differences
+        .iter()
+        .any(|difference| matches!(difference, Difference::Added(_)))
+}
+
+fn comparable_words(text: &TextData) -> Vec<String> {
+    let lines: Vec<String> = text
+        .lines()
+        .iter()
+        .map(|line| line.trim().to_lowercase())
+        .skip_while(|line| {
+            line.is_empty() || line.contains("license") && line.split_whitespace().count() <= 4
+        })
+        .collect();
+    lines
+        .split(String::is_empty)
+        .filter(|paragraph| !paragraph.iter().all(|line| is_copyright_notice(line)))
+        .flatten()
+        .flat_map(|line| line.split_whitespace())
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_string()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn is_copyright_notice(line: &str) -> bool {
+    line.starts_with("copyright")
+        || ["(c)", "©"].iter().any(|symbol| {
+            line.strip_prefix(symbol)
+                .is_some_and(|rest| rest.trim_start().starts_with(|c: char| c.is_ascii_digit()))
+        })
+}
+
+fn word_differences(words: &[String], reference_words: &[String]) -> Vec<Difference> {
+    let columns = reference_words.len() + 1;
+    let mut common = vec![0_u16; (words.len() + 1) * columns];
+    for i in (0..words.len()).rev() {
+        for j in (0..reference_words.len()).rev() {
+            common[i * columns + j] = if words[i] == reference_words[j] {
+                common[(i + 1) * columns + j + 1] + 1
+            } else {
+                common[(i + 1) * columns + j].max(common[i * columns + j + 1])
+            };
+        }
+    }
+
+    let mut differences = Vec::new();
+    let (mut added, mut removed) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    while i < words.len() || j < reference_words.len() {
+        if i < words.len() && j < reference_words.len() && words[i] == reference_words[j] {
+            push_passages(&mut differences, &mut added, &mut removed);
+            i += 1;
+            j += 1;
+        } else if j < reference_words.len()
+            && (i == words.len() || common[i * columns + j + 1] >= common[(i + 1) * columns + j])
+        {
+            removed.push(reference_words[j].as_str());
+            j += 1;
+        } else {
+            added.push(words[i].as_str());
+            i += 1;
+        }
+    }
+    push_passages(&mut differences, &mut added, &mut removed);
+    differences
+}
+
+fn push_passages(
+    differences: &mut Vec<Difference>,
+    added: &mut Vec<&str>,
+    removed: &mut Vec<&str>,
+) {
+    if !removed.is_empty() {
+        differences.push(Difference::Removed(removed.join(" ")));
+        removed.clear();
+    }
+    if !added.is_empty() {
+        differences.push(Difference::Added(added.join(" ")));
+        added.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::licenses::texts::LICENSE_TEXTS;
+
+    #[test]
+    fn identical_texts_have_no_differences() {
+        let mit = TextData::new(LICENSE_TEXTS["MIT"]);
+        assert!(differing_passages(&mit, &mit).is_empty());
+    }
+
+    #[test]
+    fn copyright_notices_are_not_differences() {
+        let text = LICENSE_TEXTS["MIT"].replace(
+            "Copyright (c) <year> <copyright holders>",
+            "Copyright (c) 2024 Example Author",
+        );
+        assert!(
+            differing_passages(&TextData::new(&text), &TextData::new(LICENSE_TEXTS["MIT"]))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn titles_are_not_differences() {
+        let text = LICENSE_TEXTS["MIT"].replace("MIT License", "The MIT License (MIT)");
+        assert!(
+            differing_passages(&TextData::new(&text), &TextData::new(LICENSE_TEXTS["MIT"]))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn added_and_removed_passages_are_found() {
+        let text = LICENSE_TEXTS["MIT"]
+            .replace(
+                "free of charge,",
+                "free of charge, for non-commercial use only,",
+            )
+            .replace("merge, publish,", "merge,");
+
+        let differences =
+            differing_passages(&TextData::new(&text), &TextData::new(LICENSE_TEXTS["MIT"]));
+
+        assert_eq!(
+            vec![
+                Difference::Added("for non-commercial use only".to_string()),
+                Difference::Removed("publish".to_string()),
+            ],
+            differences
+        );
+        assert!(has_additions(&differences));
+    }
+}
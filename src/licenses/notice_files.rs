@@ -0,0 +1,271 @@
+use crate::cargo_metadata::Package;
+use crate::config::CrateConfig;
+use crate::file_io::{DirEntry, FileIO};
+use crate::licenses::License;
+use crate::licenses::collect::{is_skipped_file, skipped_files_for_package};
+use crate::licenses::prefer::preferred_license;
+use crate::licenses::status::serialize_crate_files;
+use crate::log::warning;
+use colored::Colorize;
+use itertools::Itertools;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+const APACHE_2: &str = "Apache-2.0";
+
+pub fn is_notice_file(dir_entry: &DirEntry) -> bool {
+    dir_entry.is_file
+        && Path::new(&dir_entry.name)
+            .file_stem()
+            .is_some_and(|stem| stem.eq_ignore_ascii_case("notice"))
+}
+
+pub fn find_notice_files(
+    file_io: &impl FileIO,
+    packages: &[Package],
+    crate_configs: &HashMap<String, CrateConfig>,
+) -> anyhow::Result<HashMap<Package, Vec<DirEntry>>> {
+    packages
+        .iter()
+        .map(|package| {
+            let skipped_files = skipped_files_for_package(package, crate_configs);
+            let notice_files = package_notice_files(file_io, package)?
+                .into_iter()
+                .filter(|dir_entry| !is_skipped_file(dir_entry, skipped_files))
+                .collect();
+            Ok((package.clone(), notice_files))
+        })
+        .collect()
+}
+
+fn package_notice_files(file_io: &impl FileIO, package: &Package) -> anyhow::Result<Vec<DirEntry>> {
+    Ok(file_io
+        .read_dir(package.path.as_ref())?
+        .into_iter()
+        .filter(is_notice_file)
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .collect())
+}
+
+pub fn with_notice_files(
+    mut licenses: HashMap<Package, Vec<DirEntry>>,
+    notice_files: HashMap<Package, Vec<DirEntry>>,
+) -> HashMap<Package, Vec<DirEntry>> {
+    for (package, notices) in notice_files {
+        licenses.entry(package).or_default().extend(notices);
+    }
+    licenses
+}
+
+#[cfg_attr(test, derive(Debug, PartialEq, Eq))]
+pub struct MissingNotices(pub HashMap<Package, Vec<DirEntry>>);
+
+impl MissingNotices {
+    pub fn any(&self) -> bool {
+        self.0.values().any(|notices| !notices.is_empty())
+    }
+}
+
+impl Serialize for MissingNotices {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_crate_files(
+            &self.0,
+            |notice| notice.name.to_string_lossy().to_string(),
+            serializer,
+        )
+    }
+}
+
+impl Display for MissingNotices {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.any() {
+            return Ok(());
+        }
+        writeln!(
+            f,
+            "{}",
+            warning(&format!(
+                "{} - {APACHE_2} requires redistributing the NOTICE files skipped for:",
+                "missing notice".bold()
+            ))
+        )?;
+        for (package, notices) in self
+            .0
+            .iter()
+            .filter(|(_, notices)| !notices.is_empty())
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        {
+            writeln!(
+                f,
+                "\t{} - {}",
+                package.normalised_name.bold(),
+                notices
+                    .iter()
+                    .map(|notice| notice.name.to_string_lossy())
+                    .join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn find_missing_notices(
+    file_io: &impl FileIO,
+    packages: &[Package],
+    preferences: &[String],
+    crate_configs: &HashMap<String, CrateConfig>,
+) -> anyhow::Result<MissingNotices> {
+    packages
+        .iter()
+        .filter(|package| is_apache_licensed(package, preferences, crate_configs))
+        .map(|package| {
+            let skipped_files = skipped_files_for_package(package, crate_configs);
+            let missing = package_notice_files(file_io, package)?
+                .into_iter()
+                .filter(|dir_entry| is_skipped_file(dir_entry, skipped_files))
+                .collect();
+            Ok((package.clone(), missing))
+        })
+        .collect::<anyhow::Result<_>>()
+        .map(MissingNotices)
+}
+
+fn is_apache_licensed(
+    package: &Package,
+    preferences: &[String],
+    crate_configs: &HashMap<String, CrateConfig>,
+) -> bool {
+    preferred_license(package, preferences, crate_configs)
+        .or_else(|| package.license.as_deref().map(License::parse))
+        .is_some_and(|license| license.ids().iter().any(|id| id == APACHE_2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::FileIOSpy;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    fn entry(name: &str, is_file: bool) -> DirEntry {
+        DirEntry {
+            name: OsString::from(name),
+            path: PathBuf::from(name),
+            is_file,
+        }
+    }
+
+    fn package(name: &str, license: &str) -> Package {
+        Package {
+            license: Some(license.to_string()),
+            ..Package::called(name)
+        }
+    }
+
+    fn skip_notice(name: &str) -> HashMap<String, CrateConfig> {
+        HashMap::from([(
+            name.to_string(),
+            CrateConfig {
+                skip: vec!["NOTICE".to_string()],
+                ..CrateConfig::default()
+            },
+        )])
+    }
+
+    #[test]
+    fn notice_files_are_recognised_by_name() {
+        assert!(is_notice_file(&entry("NOTICE", true)));
+        assert!(is_notice_file(&entry("notice.txt", true)));
+        assert!(!is_notice_file(&entry("NOTICE", false)));
+        assert!(!is_notice_file(&entry("NOTICES.md", true)));
+        assert!(!is_notice_file(&entry("LICENSE", true)));
+    }
+
+    #[test]
+    fn notice_files_in_crate_root_are_found() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set([Ok(vec![
+            entry("LICENSE", true),
+            entry("NOTICE", true),
+            entry("src", false),
+        ])]);
+
+        let notice_files =
+            find_notice_files(&file_io_spy, &[Package::called("example")], &HashMap::new())
+                .unwrap();
+
+        assert_eq!(
+            vec![entry("NOTICE", true)],
+            notice_files[&Package::called("example")]
+        );
+    }
+
+    #[test]
+    fn notice_files_are_added_to_licenses_of_their_crate() {
+        let licenses = HashMap::from([(Package::called("example"), vec![entry("LICENSE", true)])]);
+        let notice_files =
+            HashMap::from([(Package::called("example"), vec![entry("NOTICE", true)])]);
+
+        assert_eq!(
+            vec![entry("LICENSE", true), entry("NOTICE", true)],
+            with_notice_files(licenses, notice_files)[&Package::called("example")]
+        );
+    }
+
+    #[test]
+    fn skipped_notice_of_apache_crate_is_missing() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_dir
+            .returns
+            .set_fn(|_| Ok(vec![entry("NOTICE", true)]));
+        let example = package("example", "Apache-2.0");
+
+        assert!(
+            find_notice_files(
+                &file_io_spy,
+                std::slice::from_ref(&example),
+                &skip_notice("example")
+            )
+            .unwrap()[&example]
+                .is_empty()
+        );
+        assert_eq!(
+            MissingNotices(HashMap::from([(
+                example.clone(),
+                vec![entry("NOTICE", true)]
+            )])),
+            find_missing_notices(&file_io_spy, &[example], &[], &skip_notice("example")).unwrap()
+        );
+    }
+
+    #[test]
+    fn skipped_notice_is_not_missing_when_apache_is_not_chosen() {
+        let file_io_spy = FileIOSpy::default();
+
+        let missing = find_missing_notices(
+            &file_io_spy,
+            &[package("example", "MIT OR Apache-2.0")],
+            &["MIT".to_string()],
+            &skip_notice("example"),
+        )
+        .unwrap();
+
+        assert!(!missing.any());
+        assert!(file_io_spy.read_dir.arguments.take().is_empty());
+    }
+
+    #[test]
+    fn display_missing_notices() {
+        let missing = MissingNotices(HashMap::from([(
+            Package::called("example"),
+            vec![entry("NOTICE", true)],
+        )]));
+
+        let display = strip_ansi_escapes::strip_str(missing.to_string());
+        assert!(display.starts_with("warning: missing notice"));
+        assert!(display.contains("example - NOTICE"));
+    }
+}
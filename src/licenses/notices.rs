@@ -0,0 +1,249 @@
+use crate::cargo_metadata::Package;
+use crate::config::{CrateConfig, IncludedLicense};
+use crate::file_io::{DirEntry, FileIO};
+use crate::licenses::copyright::package_copyright;
+use crate::licenses::prefer::preferred_license;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+const SEPARATOR_WIDTH: usize = 80;
+
+pub fn aggregate_notices(
+    file_io: &impl FileIO,
+    all_licenses: &HashMap<Package, Vec<DirEntry>>,
+    preferences: &[String],
+    crate_configs: &HashMap<String, CrateConfig>,
+) -> anyhow::Result<String> {
+    all_licenses
+        .iter()
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(package, licenses)| {
+            notice_for_package(file_io, package, licenses, preferences, crate_configs)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map(|notices| notices.join("\n"))
+}
+
+fn notice_for_package(
+    file_io: &impl FileIO,
+    package: &Package,
+    licenses: &[DirEntry],
+    preferences: &[String],
+    crate_configs: &HashMap<String, CrateConfig>,
+) -> anyhow::Result<String> {
+    let copyright = package_copyright(file_io, package, licenses)?;
+    let mut notice = format!(
+        "{}\n{} - {}\n",
+        "=".repeat(SEPARATOR_WIDTH),
+        package.normalised_name,
+        license_description(package, preferences, crate_configs),
+    );
+    for statement in &copyright.statements {
+        notice.push_str(statement);
+        notice.push('\n');
+    }
+    if !copyright.authors.is_empty() {
+        notice.push_str("Authors: ");
+        notice.push_str(&copyright.authors.join(", "));
+        notice.push('\n');
+    }
+    notice.push_str(&"=".repeat(SEPARATOR_WIDTH));
+    notice.push('\n');
+
+    for license in licenses.iter().sorted_by(|a, b| a.name.cmp(&b.name)) {
+        notice.push('\n');
+        notice.push_str(file_io.read_file(&license.path)?.trim_end());
+        notice.push('\n');
+    }
+
+    if let Some(config) = crate_configs.get(&package.normalised_name) {
+        for included_license in &config.include {
+            match included_license {
+                IncludedLicense::Text { text, .. } => {
+                    notice.push('\n');
+                    notice.push_str(text.trim_end());
+                    notice.push('\n');
+                }
+            }
+        }
+    }
+
+    Ok(notice)
+}
+
+fn license_description(
+    package: &Package,
+    preferences: &[String],
+    crate_configs: &HashMap<String, CrateConfig>,
+) -> String {
+    let Some(declared) = &package.license else {
+        return "no declared license".to_string();
+    };
+    match preferred_license(package, preferences, crate_configs) {
+        Some(preferred) if preferred.to_string() != *declared => {
+            format!("{preferred} (chosen from {declared})")
+        }
+        _ => declared.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::FileIOSpy;
+    use cargo_metadata::camino::Utf8PathBuf;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    fn package(name: &str, license: Option<&str>) -> Package {
+        Package {
+            normalised_name: name.to_string(),
+            path: Utf8PathBuf::default(),
+            url: None,
+            license: license.map(ToString::to_string),
+            authors: Vec::new(),
+            source: None,
+        }
+    }
+
+    #[test]
+    fn no_packages_produce_no_notices() {
+        let file_io_spy = FileIOSpy::default();
+        assert!(
+            aggregate_notices(&file_io_spy, &HashMap::new(), &[], &HashMap::new())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn failure_to_read_license_causes_error() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|_| Ok(Vec::new()));
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_| Err(anyhow::anyhow!("deliberate test error")));
+
+        let all_licenses = HashMap::from([(
+            package("example", Some("MIT")),
+            vec![DirEntry {
+                name: OsString::from("LICENSE"),
+                path: PathBuf::from("example/LICENSE"),
+                is_file: true,
+            }],
+        )]);
+
+        assert!(aggregate_notices(&file_io_spy, &all_licenses, &[], &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn notice_contains_license_text_and_included_licenses() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|_| Ok(Vec::new()));
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_| Ok("license text\n".to_string()));
+
+        let all_licenses = HashMap::from([(
+            package("example", Some("MIT")),
+            vec![DirEntry {
+                name: OsString::from("LICENSE"),
+                path: PathBuf::from("example/LICENSE"),
+                is_file: true,
+            }],
+        )]);
+        let crate_configs = HashMap::from([(
+            "example".to_string(),
+            CrateConfig {
+                include: vec![IncludedLicense::Text {
+                    name: "NOTICE".to_string(),
+                    text: "included text".to_string(),
+                }],
+                ..CrateConfig::default()
+            },
+        )]);
+
+        let separator = "=".repeat(SEPARATOR_WIDTH);
+        assert_eq!(
+            format!("{separator}\nexample - MIT\n{separator}\n\nlicense text\n\nincluded text\n"),
+            aggregate_notices(&file_io_spy, &all_licenses, &[], &crate_configs).unwrap()
+        );
+    }
+
+    #[test]
+    fn notices_are_ordered_by_crate_name() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|_| Ok(Vec::new()));
+
+        let all_licenses = HashMap::from([
+            (package("beta", Some("MIT")), vec![]),
+            (package("alpha", None), vec![]),
+        ]);
+
+        let notices = aggregate_notices(&file_io_spy, &all_licenses, &[], &HashMap::new()).unwrap();
+
+        assert!(
+            notices.find("alpha - no declared license").unwrap()
+                < notices.find("beta - MIT").unwrap()
+        );
+    }
+
+    #[test]
+    fn notice_reports_chosen_license() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|_| Ok(Vec::new()));
+
+        let all_licenses = HashMap::from([(package("example", Some("MIT OR Apache-2.0")), vec![])]);
+
+        assert!(
+            aggregate_notices(
+                &file_io_spy,
+                &all_licenses,
+                &["Apache-2.0".to_string()],
+                &HashMap::new()
+            )
+            .unwrap()
+            .contains("example - Apache-2.0 (chosen from MIT OR Apache-2.0)")
+        );
+    }
+
+    #[test]
+    fn notice_names_copyright_holders() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|_| Ok(Vec::new()));
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_| Ok("Copyright (c) 2024 Example\n\nlicense text\n".to_string()));
+
+        let all_licenses = HashMap::from([
+            (
+                package("example", Some("MIT")),
+                vec![DirEntry {
+                    name: OsString::from("LICENSE"),
+                    path: PathBuf::from("example/LICENSE"),
+                    is_file: true,
+                }],
+            ),
+            (
+                Package {
+                    authors: vec!["Another <another@example.com>".to_string()],
+                    ..package("another", Some("MIT"))
+                },
+                vec![],
+            ),
+        ]);
+
+        let notices = aggregate_notices(&file_io_spy, &all_licenses, &[], &HashMap::new()).unwrap();
+
+        let separator = "=".repeat(SEPARATOR_WIDTH);
+        assert!(notices.contains(&format!(
+            "example - MIT\nCopyright (c) 2024 Example\n{separator}\n"
+        )));
+        assert!(notices.contains(&format!(
+            "another - MIT\nAuthors: Another <another@example.com>\n{separator}\n"
+        )));
+    }
+}
@@ -0,0 +1,71 @@
+const PLACEHOLDER_BRACKETS: [(char, char); 3] = [('[', ']'), ('<', '>'), ('{', '}')];
+const PLACEHOLDERS: [&str; 11] = [
+    "yyyy",
+    "year",
+    "name of copyright owner",
+    "copyright holder",
+    "copyright holders",
+    "copyright owner",
+    "owner",
+    "fullname",
+    "name of author",
+    "author",
+    "organization",
+];
+
+// the template within the appendix is part of the verbatim license text, for applying it to files
+const APACHE_APPENDIX: &str = "appendix: how to apply the apache license";
+
+pub fn unfilled_placeholders(text: &str) -> Vec<String> {
+    text.lines()
+        .take_while(|line| !line.trim().to_lowercase().starts_with(APACHE_APPENDIX))
+        .flat_map(placeholders_in)
+        .collect()
+}
+
+pub fn placeholders_in(line: &str) -> Vec<String> {
+    let mut placeholders = Vec::new();
+    for (open, close) in PLACEHOLDER_BRACKETS {
+        let mut rest = line;
+        while let Some(start) = rest.find(open) {
+            let Some(end) = rest[start..].find(close) else {
+                break;
+            };
+            let inner = rest[start + open.len_utf8()..start + end].trim();
+            if PLACEHOLDERS.contains(&inner.to_lowercase().as_str()) {
+                placeholders.push(rest[start..=start + end].to_string());
+            }
+            rest = &rest[start + end..];
+        }
+    }
+    placeholders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::licenses::texts::LICENSE_TEXTS;
+
+    #[test]
+    fn placeholders_of_license_templates_are_found() {
+        assert_eq!(
+            vec!["<year>".to_string(), "<copyright holders>".to_string()],
+            unfilled_placeholders(LICENSE_TEXTS["MIT"])
+        );
+        assert_eq!(
+            vec![
+                "[yyyy]".to_string(),
+                "[name of copyright owner]".to_string()
+            ],
+            unfilled_placeholders(
+                "Copyright [yyyy] [name of copyright owner]\n\nLicensed under the Apache License"
+            )
+        );
+        assert!(unfilled_placeholders("Copyright (c) 2024 Example").is_empty());
+    }
+
+    #[test]
+    fn template_in_the_apache_appendix_is_not_unfilled() {
+        assert!(unfilled_placeholders(LICENSE_TEXTS["Apache-2.0"]).is_empty());
+    }
+}
@@ -0,0 +1,256 @@
+use crate::cargo_metadata::Package;
+use crate::config::CrateConfig;
+use crate::file_io::{DirEntry, FileIO};
+use crate::licenses::License;
+use crate::licenses::texts::LicenseTexts;
+use crate::licenses::validate::{ConfidenceThreshold, declared_license_files};
+use std::collections::HashMap;
+
+pub fn preferred_license(
+    package: &Package,
+    preferences: &[String],
+    crate_configs: &HashMap<String, CrateConfig>,
+) -> Option<License> {
+    let preferences = preferences_for_package(package, preferences, crate_configs);
+    if preferences.is_empty() {
+        return None;
+    }
+    package
+        .license
+        .as_deref()
+        .map(|license| License::parse(license).preferred_branch(preferences))
+}
+
+pub fn with_preferred_licenses(
+    packages: Vec<Package>,
+    preferences: &[String],
+    crate_configs: &HashMap<String, CrateConfig>,
+) -> Vec<Package> {
+    packages
+        .into_iter()
+        .map(|mut package| {
+            if let Some(license) = preferred_license(&package, preferences, crate_configs) {
+                package.license = Some(license.to_string());
+            }
+            package
+        })
+        .collect()
+}
+
+pub fn select_preferred_licenses(
+    file_io: &impl FileIO,
+    license_texts: &LicenseTexts,
+    confidence_threshold: ConfidenceThreshold,
+    all_licenses: HashMap<Package, Vec<DirEntry>>,
+    preferences: &[String],
+    crate_configs: &HashMap<String, CrateConfig>,
+) -> HashMap<Package, Vec<DirEntry>> {
+    all_licenses
+        .into_iter()
+        .map(|(package, licenses)| {
+            let Some(preferred) = preferred_license(&package, preferences, crate_configs) else {
+                return (package, licenses);
+            };
+            let preferred_ids = preferred.ids();
+            let declared = License::parse(package.license.as_deref().unwrap_or_default());
+            let licenses = declared_license_files(
+                file_io,
+                license_texts,
+                confidence_threshold.for_package(&package, crate_configs),
+                &declared,
+                &licenses,
+            )
+            .into_iter()
+            .filter(|(_, ids)| ids.is_empty() || ids.iter().any(|id| preferred_ids.contains(id)))
+            .map(|(entry, _)| entry)
+            .collect();
+            (package, licenses)
+        })
+        .collect()
+}
+
+fn preferences_for_package<'a>(
+    package: &Package,
+    preferences: &'a [String],
+    crate_configs: &'a HashMap<String, CrateConfig>,
+) -> &'a [String] {
+    crate_configs
+        .get(&package.normalised_name)
+        .map(|config| config.prefer.as_slice())
+        .filter(|crate_preferences| !crate_preferences.is_empty())
+        .unwrap_or(preferences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::FileIOSpy;
+    use crate::licenses::texts::LICENSE_TEXTS;
+    use cargo_metadata::camino::Utf8PathBuf;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    fn dual_licensed(name: &str) -> Package {
+        Package {
+            normalised_name: name.to_string(),
+            path: Utf8PathBuf::default(),
+            url: None,
+            license: Some("MIT OR Apache-2.0".to_string()),
+            authors: Vec::new(),
+            source: None,
+        }
+    }
+
+    fn license_file(name: &str) -> DirEntry {
+        DirEntry {
+            name: OsString::from(name),
+            path: PathBuf::from(name),
+            is_file: true,
+        }
+    }
+
+    #[test]
+    fn no_preferred_license_without_preferences() {
+        assert!(preferred_license(&dual_licensed("example"), &[], &HashMap::new()).is_none());
+    }
+
+    #[test]
+    fn global_preference_chooses_license() {
+        assert_eq!(
+            Some(License::parse("Apache-2.0")),
+            preferred_license(
+                &dual_licensed("example"),
+                &["Apache-2.0".to_string()],
+                &HashMap::new()
+            )
+        );
+    }
+
+    #[test]
+    fn crate_preference_overrides_global_preference() {
+        let crate_configs = HashMap::from([(
+            "example".to_string(),
+            CrateConfig {
+                prefer: vec!["MIT".to_string()],
+                ..CrateConfig::default()
+            },
+        )]);
+
+        assert_eq!(
+            Some(License::parse("MIT")),
+            preferred_license(
+                &dual_licensed("example"),
+                &["Apache-2.0".to_string()],
+                &crate_configs
+            )
+        );
+        assert_eq!(
+            Some(License::parse("Apache-2.0")),
+            preferred_license(
+                &dual_licensed("other"),
+                &["Apache-2.0".to_string()],
+                &crate_configs
+            )
+        );
+    }
+
+    #[test]
+    fn packages_take_preferred_license() {
+        assert_eq!(
+            Some("MIT".to_string()),
+            with_preferred_licenses(
+                vec![dual_licensed("example")],
+                &["MIT".to_string()],
+                &HashMap::new()
+            )[0]
+            .license
+        );
+    }
+
+    #[test]
+    fn only_files_for_preferred_license_are_selected() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_string_lossy().as_ref() {
+                "LICENSE-MIT" => LICENSE_TEXTS["MIT"].to_string(),
+                "LICENSE-APACHE" => LICENSE_TEXTS["Apache-2.0"].to_string(),
+                _ => "not a license".to_string(),
+            })
+        });
+
+        let all_licenses = HashMap::from([(
+            dual_licensed("example"),
+            vec![
+                license_file("LICENSE-MIT"),
+                license_file("LICENSE-APACHE"),
+                license_file("COPYRIGHT"),
+            ],
+        )]);
+
+        let mut selected = select_preferred_licenses(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            all_licenses,
+            &["MIT".to_string()],
+            &HashMap::new(),
+        )
+        .remove(&dual_licensed("example"))
+        .unwrap();
+        selected.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(
+            vec![license_file("COPYRIGHT"), license_file("LICENSE-MIT")],
+            selected
+        );
+    }
+
+    #[test]
+    fn all_files_are_selected_without_preferences() {
+        let file_io_spy = FileIOSpy::default();
+
+        let all_licenses = HashMap::from([(
+            dual_licensed("example"),
+            vec![license_file("LICENSE-MIT"), license_file("LICENSE-APACHE")],
+        )]);
+
+        assert_eq!(
+            all_licenses.clone(),
+            select_preferred_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                all_licenses,
+                &[],
+                &HashMap::new(),
+            )
+        );
+        assert!(file_io_spy.read_file.arguments.take().is_empty());
+    }
+
+    #[test]
+    fn file_concatenating_preferred_license_is_selected() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|_: &PathBuf| {
+            Ok(format!(
+                "{}\n\n{}",
+                LICENSE_TEXTS["Apache-2.0"], LICENSE_TEXTS["MIT"]
+            ))
+        });
+
+        let all_licenses =
+            HashMap::from([(dual_licensed("example"), vec![license_file("LICENSE")])]);
+
+        assert_eq!(
+            vec![license_file("LICENSE")],
+            select_preferred_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                all_licenses,
+                &["MIT".to_string()],
+                &HashMap::new(),
+            )[&dual_licensed("example")]
+        );
+    }
+}
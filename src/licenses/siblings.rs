@@ -0,0 +1,156 @@
+use crate::cargo_metadata::Package;
+use crate::file_io::{DirEntry, FileIO};
+use crate::licenses::collect::is_skipped_file;
+use crate::licenses::is_license::is_license;
+use crate::licenses::status::LicenseStatus;
+use cargo_metadata::semver::Version;
+use itertools::Itertools;
+
+pub fn sibling_licenses(
+    file_io: &impl FileIO,
+    package: &Package,
+    skipped_files: &[String],
+) -> Vec<DirEntry> {
+    let (Some(parent), Some((prefix, version))) = (
+        package.path.parent(),
+        package.path.file_name().and_then(split_version),
+    ) else {
+        return Vec::new();
+    };
+    let Ok(entries) = file_io.read_dir(parent.as_ref()) else {
+        return Vec::new();
+    };
+    let (newer, older): (Vec<_>, Vec<_>) = entries
+        .into_iter()
+        .filter(|dir_entry| !dir_entry.is_file)
+        .filter_map(|dir_entry| {
+            let (sibling_prefix, sibling_version) = split_version(dir_entry.name.to_str()?)?;
+            (sibling_prefix == prefix && sibling_version != version)
+                .then_some((sibling_version, dir_entry))
+        })
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .partition(|(sibling_version, _)| *sibling_version > version);
+    let siblings = newer.into_iter().chain(older.into_iter().rev());
+    for (_, sibling) in siblings {
+        let licenses: Vec<DirEntry> = file_io
+            .read_dir(&sibling.path)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|dir_entry| is_license(dir_entry) && !is_skipped_file(dir_entry, skipped_files))
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .collect();
+        if !licenses.is_empty() {
+            return licenses;
+        }
+    }
+    Vec::new()
+}
+
+fn split_version(directory_name: &str) -> Option<(&str, Version)> {
+    directory_name.match_indices('-').find_map(|(index, _)| {
+        Version::parse(&directory_name[index + 1..])
+            .ok()
+            .map(|version| (&directory_name[..index], version))
+    })
+}
+
+pub fn sibling_version(package: &Package, licenses: &[DirEntry]) -> Option<String> {
+    if package.is_git_or_path() {
+        return None;
+    }
+    licenses
+        .iter()
+        .find(|dir_entry| !dir_entry.path.starts_with(&package.path))
+        .and_then(|dir_entry| dir_entry.path.parent()?.file_name()?.to_str())
+        .and_then(split_version)
+        .map(|(_, version)| version.to_string())
+}
+
+pub fn with_sibling_version(
+    license_status: LicenseStatus,
+    package: &Package,
+    licenses: &[DirEntry],
+) -> LicenseStatus {
+    match sibling_version(package, licenses) {
+        Some(version) if license_status.is_valid() => LicenseStatus::SiblingVersion(version),
+        _ => license_status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::FileIOSpy;
+    use cargo_metadata::camino::Utf8PathBuf;
+    use std::ffi::OsString;
+    use std::path::{Path, PathBuf};
+
+    fn entry(directory: &str, name: &str, is_file: bool) -> DirEntry {
+        DirEntry {
+            name: OsString::from(name),
+            path: Path::new(directory).join(name),
+            is_file,
+        }
+    }
+
+    fn package() -> Package {
+        Package {
+            path: Utf8PathBuf::from("registry/example-crate-1.0.0"),
+            source: Some("registry+https://github.com/rust-lang/crates.io-index".to_string()),
+            ..Package::called("example_crate")
+        }
+    }
+
+    #[test]
+    fn licenses_are_taken_from_the_closest_newer_sibling_version() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "registry" => vec![
+                    entry("registry", "example-crate-0.9.0", false),
+                    entry("registry", "example-crate-1.0.0", false),
+                    entry("registry", "example-crate-1.2.0", false),
+                    entry("registry", "example-crate-1.1.0", false),
+                    entry("registry", "example-crate-extra-1.1.0", false),
+                ],
+                "registry/example-crate-1.1.0" => Vec::new(),
+                directory => vec![entry(directory, "LICENSE", true)],
+            })
+        });
+
+        let licenses = sibling_licenses(&file_io_spy, &package(), &[]);
+
+        assert_eq!(
+            vec![entry("registry/example-crate-1.2.0", "LICENSE", true)],
+            licenses
+        );
+        assert_eq!(
+            LicenseStatus::SiblingVersion("1.2.0".to_string()),
+            with_sibling_version(LicenseStatus::Valid, &package(), &licenses)
+        );
+        assert_eq!(
+            LicenseStatus::TooFew,
+            with_sibling_version(LicenseStatus::TooFew, &package(), &licenses)
+        );
+    }
+
+    #[test]
+    fn older_sibling_versions_are_used_without_newer_ones() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "registry" => vec![
+                    entry("registry", "example-crate-0.8.0", false),
+                    entry("registry", "example-crate-0.9.0", false),
+                    entry("registry", "example-crate-1.0.0", false),
+                ],
+                directory => vec![entry(directory, "LICENSE-MIT", true)],
+            })
+        });
+
+        assert_eq!(
+            vec![entry("registry/example-crate-0.9.0", "LICENSE-MIT", true)],
+            sibling_licenses(&file_io_spy, &package(), &[])
+        );
+    }
+}
@@ -1,13 +1,22 @@
 use crate::cargo_metadata::Package;
-use crate::log::warning;
+use crate::file_io::DirEntry;
+use crate::licenses::embedded::EmbeddedLicenses;
+use crate::licenses::inherited::InheritedLicenses;
+use crate::licenses::modified::Difference;
+use crate::licenses::notice_files::MissingNotices;
+use crate::licenses::texts::Identified;
+use crate::licenses::unused::UnusedConfigs;
+use crate::log::{note, warning};
 use colored::Colorize;
 use itertools::Itertools;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, Serializer};
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::mem::discriminant;
 
-#[derive(PartialEq, Eq, Hash, Debug, Deserialize, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, Hash, Debug, Deserialize, PartialOrd, Ord, Clone)]
 pub enum LicenseStatus {
     #[serde(skip)]
     Valid,
@@ -15,12 +24,155 @@ pub enum LicenseStatus {
     Empty,
     #[serde(rename = "none declared")]
     NoneDeclared,
+    #[serde(rename = "unreadable")]
+    Unreadable(Vec<ReportedFile>),
     #[serde(rename = "too few")]
     TooFew,
     #[serde(rename = "additional")]
-    Additional(Vec<String>),
+    Additional(Vec<ReportedFile>),
     #[serde(rename = "mismatch")]
-    Mismatch(Vec<String>),
+    Mismatch(Vec<ReportedFile>),
+    #[serde(rename = "misnamed")]
+    Misnamed(Vec<ReportedFile>),
+    #[serde(rename = "unfilled template")]
+    UnfilledTemplate(Vec<ReportedFile>),
+    #[serde(rename = "missing exception")]
+    MissingException(Vec<String>),
+    #[serde(rename = "undeclared exception")]
+    UndeclaredException(Vec<String>),
+    #[serde(rename = "undeclared source license")]
+    UndeclaredSourceLicense(Vec<String>),
+    #[serde(rename = "sibling version")]
+    SiblingVersion(String),
+    #[serde(skip)]
+    Modified(Vec<ReportedFile>),
+    #[serde(skip)]
+    Satisfied(String),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(from = "String")]
+pub struct ReportedFile {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detected: Option<Identified>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub differences: Vec<Difference>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub placeholders: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<String> for ReportedFile {
+    fn from(name: String) -> Self {
+        Self {
+            name,
+            score: None,
+            detected: None,
+            differences: Vec::new(),
+            placeholders: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+impl From<&str> for ReportedFile {
+    fn from(name: &str) -> Self {
+        Self::from(name.to_string())
+    }
+}
+
+impl PartialEq for ReportedFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl Eq for ReportedFile {}
+
+impl Hash for ReportedFile {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl PartialOrd for ReportedFile {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ReportedFile {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name.cmp(&other.name)
+    }
+}
+
+impl Display for ReportedFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let notes: Vec<String> = self
+            .score
+            .map(|score| format!("score {score:.2}"))
+            .into_iter()
+            .chain(
+                self.detected
+                    .as_ref()
+                    .map(|detected| format!("detected {} {:.2}", detected.id, detected.score)),
+            )
+            .chain(self.placeholders.iter().cloned())
+            .chain(self.error.clone())
+            .collect();
+        if notes.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{} ({})", self.name, notes.join(", "))
+        }
+    }
+}
+
+impl LicenseStatus {
+    pub const fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid | Self::Modified(_) | Self::Satisfied(_))
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Valid => "valid",
+            Self::Empty => "empty",
+            Self::NoneDeclared => "none declared",
+            Self::Unreadable(_) => "unreadable",
+            Self::TooFew => "too few",
+            Self::Additional(_) => "additional",
+            Self::Mismatch(_) => "mismatch",
+            Self::Misnamed(_) => "misnamed",
+            Self::UnfilledTemplate(_) => "unfilled template",
+            Self::MissingException(_) => "missing exception",
+            Self::UndeclaredException(_) => "undeclared exception",
+            Self::UndeclaredSourceLicense(_) => "undeclared source license",
+            Self::SiblingVersion(_) => "sibling version",
+            Self::Modified(_) => "modified",
+            Self::Satisfied(_) => "satisfied",
+        }
+    }
+
+    pub fn details(&self) -> Option<String> {
+        match self {
+            Self::Unreadable(licenses)
+            | Self::Additional(licenses)
+            | Self::Mismatch(licenses)
+            | Self::Misnamed(licenses)
+            | Self::UnfilledTemplate(licenses)
+            | Self::Modified(licenses) => Some(licenses.iter().sorted().join(", ")),
+            Self::MissingException(licenses)
+            | Self::UndeclaredException(licenses)
+            | Self::UndeclaredSourceLicense(licenses) => Some(licenses.iter().sorted().join(", ")),
+            Self::SiblingVersion(version) | Self::Satisfied(version) => Some(version.clone()),
+            _ => None,
+        }
+    }
 }
 
 impl Display for LicenseStatus {
@@ -28,25 +180,70 @@ impl Display for LicenseStatus {
         match self {
             Self::Valid => Ok(()),
             Self::Empty => {
-                writeln!(f, "{} - did not find any licenses for:", "empty".bold())
+                writeln!(f, "{} - did not find any licenses for:", self.name().bold())
             }
             Self::NoneDeclared => {
-                writeln!(f, "{} - no declared licenses for:", "none declared".bold())
+                writeln!(f, "{} - no declared licenses for:", self.name().bold())
             }
+            Self::Unreadable(_) => writeln!(
+                f,
+                "{} - could not read license file(s) as text for:",
+                self.name().bold()
+            ),
             Self::TooFew => writeln!(
                 f,
                 "{} - did not find as many licenses as declared for:",
-                "too few".bold()
+                self.name().bold()
             ),
             Self::Additional(_) => writeln!(
                 f,
                 "{} - found all declared licenses, but found additional licenses for:",
-                "additional".bold()
+                self.name().bold()
             ),
             Self::Mismatch(_) => writeln!(
                 f,
                 "{} - found license(s) whose content was not similar to declared licenses for:",
-                "mismatch".bold()
+                self.name().bold()
+            ),
+            Self::Misnamed(_) => writeln!(
+                f,
+                "{} - found license(s) named after a different declared license than their content for:",
+                self.name().bold()
+            ),
+            Self::UnfilledTemplate(_) => writeln!(
+                f,
+                "{} - found license(s) with unfilled template placeholders and no copyright holder for:",
+                self.name().bold()
+            ),
+            Self::MissingException(_) => writeln!(
+                f,
+                "{} - did not find the text of declared license exception(s) for:",
+                self.name().bold()
+            ),
+            Self::UndeclaredException(_) => writeln!(
+                f,
+                "{} - found license exception(s) that were not declared for:",
+                self.name().bold()
+            ),
+            Self::UndeclaredSourceLicense(_) => writeln!(
+                f,
+                "{} - found source files under licenses not covered by the declared licenses for:",
+                self.name().bold()
+            ),
+            Self::SiblingVersion(_) => writeln!(
+                f,
+                "{} - found licenses only in another cached version of the crate for:",
+                self.name().bold()
+            ),
+            Self::Modified(_) => writeln!(
+                f,
+                "{} - found license(s) differing from the reference text of their license for:",
+                self.name().bold()
+            ),
+            Self::Satisfied(_) => writeln!(
+                f,
+                "{} - found licenses for only some alternatives of the declared licenses for:",
+                self.name().bold()
             ),
         }
     }
@@ -57,12 +254,10 @@ pub struct LicenseStatuses(pub HashMap<Package, LicenseStatus>);
 
 impl LicenseStatuses {
     pub fn any_invalid(&self) -> bool {
-        self.0
-            .values()
-            .any(|status| *status != LicenseStatus::Valid)
+        self.0.values().any(|status| !status.is_valid())
     }
 
-    fn invalid_statuses(&self) -> impl Iterator<Item = &LicenseStatus> {
+    fn reported_statuses(&self) -> impl Iterator<Item = &LicenseStatus> {
         let mut seen = HashSet::new();
         self.0
             .values()
@@ -86,7 +281,11 @@ impl LicenseStatuses {
         f: &mut Formatter<'_>,
         license_status: &LicenseStatus,
     ) -> std::fmt::Result {
-        write!(f, "{}", warning(&format!("{license_status}")))?;
+        if license_status.is_valid() {
+            write!(f, "{}", note(&format!("{license_status}")))?;
+        } else {
+            write!(f, "{}", warning(&format!("{license_status}")))?;
+        }
         for (package, status) in self.packages_with_status(license_status) {
             Self::display_status_item(f, package, status)?;
         }
@@ -98,15 +297,10 @@ impl LicenseStatuses {
         package: &Package,
         status: &LicenseStatus,
     ) -> std::fmt::Result {
-        use LicenseStatus::{Additional, Empty, Mismatch};
-
         write!(f, "\t{}", package.normalised_name.bold())?;
 
         match status {
-            Additional(licenses) | Mismatch(licenses) => {
-                writeln!(f, " - {}", licenses.iter().sorted().join(", "))
-            }
-            Empty => writeln!(
+            LicenseStatus::Empty => writeln!(
                 f,
                 " - {}",
                 package.url.as_ref().map_or_else(
@@ -114,14 +308,107 @@ impl LicenseStatuses {
                     |url| format!("try looking here: {url}")
                 )
             ),
-            _ => writeln!(f),
+            _ => match status.details() {
+                Some(details) => writeln!(f, " - {details}"),
+                None => writeln!(f),
+            },
+        }?;
+
+        if let LicenseStatus::Modified(files) = status {
+            for file in files.iter().sorted() {
+                for difference in &file.differences {
+                    writeln!(f, "\t\t{}: {difference}", file.name)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct StatusReport<'a> {
+    crates: Vec<CrateReport<'a>>,
+}
+
+#[derive(Serialize)]
+struct CrateReport<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    license: Option<&'a str>,
+    status: &'static str,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    files: &'a [ReportedFile],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<String>,
+}
+
+impl Serialize for LicenseStatuses {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StatusReport {
+            crates: self
+                .0
+                .iter()
+                .sorted()
+                .map(|(package, status)| {
+                    let files = match status {
+                        LicenseStatus::Unreadable(files)
+                        | LicenseStatus::Additional(files)
+                        | LicenseStatus::Mismatch(files)
+                        | LicenseStatus::Misnamed(files)
+                        | LicenseStatus::UnfilledTemplate(files)
+                        | LicenseStatus::Modified(files) => files.as_slice(),
+                        _ => &[],
+                    };
+                    CrateReport {
+                        name: &package.normalised_name,
+                        license: package.license.as_deref(),
+                        status: status.name(),
+                        files,
+                        details: files.is_empty().then(|| status.details()).flatten(),
+                    }
+                })
+                .collect(),
         }
+        .serialize(serializer)
     }
 }
 
+#[derive(Serialize)]
+pub struct CheckReport<'a> {
+    #[serde(flatten)]
+    pub statuses: &'a LicenseStatuses,
+    pub unused: &'a UnusedConfigs,
+    pub inherited: &'a InheritedLicenses,
+    pub embedded: &'a EmbeddedLicenses,
+    pub missing_notices: &'a MissingNotices,
+}
+
+#[derive(Serialize)]
+struct CrateFiles<'a> {
+    name: &'a str,
+    files: Vec<String>,
+}
+
+pub fn serialize_crate_files<S: Serializer>(
+    crate_files: &HashMap<Package, Vec<DirEntry>>,
+    file_name: impl Fn(&DirEntry) -> String,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(
+        crate_files
+            .iter()
+            .filter(|(_, files)| !files.is_empty())
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(package, files)| CrateFiles {
+                name: &package.normalised_name,
+                files: files.iter().map(&file_name).collect(),
+            }),
+    )
+}
+
 impl Display for LicenseStatuses {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for status in self.invalid_statuses() {
+        for status in self.reported_statuses() {
             self.display_status_section(f, status)?;
         }
 
@@ -132,6 +419,7 @@ impl Display for LicenseStatuses {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::licenses::unused::UnusedConfigReason;
     use cargo_metadata::camino::Utf8PathBuf;
 
     #[test]
@@ -149,13 +437,41 @@ mod tests {
             serde_json::from_str(r#""too few""#).unwrap()
         );
         assert_eq!(
-            LicenseStatus::Additional(vec!["file".to_string()]),
+            LicenseStatus::Additional(vec!["file".into()]),
             toml::from_str(r#"additional = ["file"]"#).unwrap()
         );
         assert_eq!(
-            LicenseStatus::Mismatch(vec!["file".to_string()]),
+            LicenseStatus::Mismatch(vec!["file".into()]),
             toml::from_str(r#"mismatch = ["file"]"#).unwrap()
         );
+        assert_eq!(
+            LicenseStatus::MissingException(vec!["LLVM-exception".to_string()]),
+            toml::from_str(r#""missing exception" = ["LLVM-exception"]"#).unwrap()
+        );
+        assert_eq!(
+            LicenseStatus::UndeclaredException(vec!["file".to_string()]),
+            toml::from_str(r#""undeclared exception" = ["file"]"#).unwrap()
+        );
+        assert_eq!(
+            LicenseStatus::UndeclaredSourceLicense(vec!["GPL-3.0-only".to_string()]),
+            toml::from_str(r#""undeclared source license" = ["GPL-3.0-only"]"#).unwrap()
+        );
+        assert_eq!(
+            LicenseStatus::Misnamed(vec!["LICENSE-MIT".into()]),
+            toml::from_str(r#"misnamed = ["LICENSE-MIT"]"#).unwrap()
+        );
+        assert_eq!(
+            LicenseStatus::Unreadable(vec!["LICENSE.pdf".into()]),
+            toml::from_str(r#"unreadable = ["LICENSE.pdf"]"#).unwrap()
+        );
+        assert_eq!(
+            LicenseStatus::UnfilledTemplate(vec!["LICENSE-APACHE".into()]),
+            toml::from_str(r#""unfilled template" = ["LICENSE-APACHE"]"#).unwrap()
+        );
+        assert_eq!(
+            LicenseStatus::SiblingVersion("1.0.1".to_string()),
+            toml::from_str(r#""sibling version" = "1.0.1""#).unwrap()
+        );
     }
 
     #[test]
@@ -195,6 +511,8 @@ mod tests {
                                 path: Utf8PathBuf::new(),
                                 url: Some("example.url".to_string()),
                                 license: None,
+                                authors: Vec::new(),
+                                source: None,
                             },
                             LicenseStatus::Empty
                         ),
@@ -204,6 +522,8 @@ mod tests {
                                 path: Utf8PathBuf::new(),
                                 url: None,
                                 license: None,
+                                authors: Vec::new(),
+                                source: None,
                             },
                             LicenseStatus::Empty
                         )
@@ -243,11 +563,7 @@ mod tests {
                 LicenseStatuses(
                     vec![(
                         Package::called("example"),
-                        LicenseStatus::Additional(vec![
-                            "a".to_string(),
-                            "b".to_string(),
-                            "c".to_string()
-                        ])
+                        LicenseStatus::Additional(vec!["a".into(), "b".into(), "c".into()])
                     ),]
                     .into_iter()
                     .collect()
@@ -301,15 +617,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn display_missing_exception_status_with_exceptions() {
+        assert_eq!(
+            "warning: missing exception - did not find the text of declared license exception(s) for:\nexample - LLVM-exception\n",
+            strip_ansi_escapes::strip_str(
+                LicenseStatuses(
+                    vec![(
+                        Package::called("example"),
+                        LicenseStatus::MissingException(vec!["LLVM-exception".to_string()])
+                    )]
+                    .into_iter()
+                    .collect()
+                )
+                .to_string()
+            )
+        );
+    }
+
     #[test]
     fn display_mismatch_status_with_files() {
         assert_eq!(
-            "warning: mismatch - found license(s) whose content was not similar to declared licenses for:\nexample - COPYING, LICENSE\n",
+            "warning: mismatch - found license(s) whose content was not similar to declared licenses for:\nexample - COPYING, LICENSE (score 0.46, detected Unlicense 0.98)\n",
             strip_ansi_escapes::strip_str(
                 LicenseStatuses(
                     vec![(
                         Package::called("example"),
-                        LicenseStatus::Mismatch(vec!["LICENSE".to_string(), "COPYING".to_string()])
+                        LicenseStatus::Mismatch(vec![
+                            ReportedFile {
+                                name: "LICENSE".to_string(),
+                                score: Some(0.4567),
+                                detected: Some(Identified {
+                                    id: "Unlicense".to_string(),
+                                    score: 0.98,
+                                }),
+                                differences: Vec::new(),
+                                placeholders: Vec::new(),
+                                error: None,
+                            },
+                            "COPYING".into()
+                        ])
                     )]
                     .into_iter()
                     .collect()
@@ -340,6 +687,65 @@ mod tests {
         assert!(!display.contains("valid_pkg"));
     }
 
+    #[test]
+    fn display_modified_status_with_differing_passages() {
+        let display = strip_ansi_escapes::strip_str(
+            LicenseStatuses(
+                vec![(
+                    Package::called("example"),
+                    LicenseStatus::Modified(vec![ReportedFile {
+                        score: Some(0.97),
+                        differences: vec![
+                            Difference::Added("for non-commercial use only".to_string()),
+                            Difference::Removed("sublicense".to_string()),
+                        ],
+                        ..ReportedFile::from("LICENSE")
+                    }]),
+                )]
+                .into_iter()
+                .collect(),
+            )
+            .to_string(),
+        );
+
+        assert!(display.contains("example - LICENSE (score 0.97)\n"));
+        assert!(display.contains("LICENSE: + for non-commercial use only\n"));
+        assert!(display.contains("LICENSE: - sublicense\n"));
+    }
+
+    #[test]
+    fn display_satisfied_status_as_note_with_branch() {
+        assert_eq!(
+            "note: satisfied - found licenses for only some alternatives of the declared licenses for:\nexample - MIT\n",
+            strip_ansi_escapes::strip_str(
+                LicenseStatuses(
+                    vec![(
+                        Package::called("example"),
+                        LicenseStatus::Satisfied("MIT".to_string())
+                    )]
+                    .into_iter()
+                    .collect()
+                )
+                .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn satisfied_status_is_not_invalid() {
+        assert!(
+            !LicenseStatuses(
+                vec![(
+                    Package::called("example"),
+                    LicenseStatus::Satisfied("MIT".to_string())
+                )]
+                .into_iter()
+                .collect()
+            )
+            .any_invalid()
+        );
+    }
+
     #[test]
     fn display_none_declared_status() {
         assert_eq!(
@@ -354,4 +760,88 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn serialize_statuses_with_reported_files() {
+        let statuses = LicenseStatuses(
+            vec![
+                (Package::called("b"), LicenseStatus::Valid),
+                (
+                    Package::called("a"),
+                    LicenseStatus::Additional(vec![ReportedFile {
+                        name: "COPYING".to_string(),
+                        score: None,
+                        detected: Some(Identified {
+                            id: "Unlicense".to_string(),
+                            score: 1.0,
+                        }),
+                        differences: Vec::new(),
+                        placeholders: Vec::new(),
+                        error: None,
+                    }]),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert_eq!(
+            serde_json::json!({
+                "crates": [
+                    {
+                        "name": "a",
+                        "status": "additional",
+                        "files": [{"name": "COPYING", "detected": {"id": "Unlicense", "score": 1.0}}]
+                    },
+                    {"name": "b", "status": "valid"}
+                ]
+            }),
+            serde_json::to_value(&statuses).unwrap()
+        );
+    }
+
+    #[test]
+    fn serialize_check_report_with_diagnostics() {
+        let statuses = LicenseStatuses(HashMap::from([(
+            Package::called("a"),
+            LicenseStatus::Valid,
+        )]));
+        let license = |path: &str| DirEntry {
+            name: std::ffi::OsString::from("LICENSE"),
+            path: std::path::PathBuf::from(path),
+            is_file: true,
+        };
+
+        let report = CheckReport {
+            statuses: &statuses,
+            unused: &UnusedConfigs(vec![
+                ("b".to_string(), UnusedConfigReason::CrateNotFound),
+                (
+                    "a".to_string(),
+                    UnusedConfigReason::SkipNotRequired(vec!["COPYING".to_string()]),
+                ),
+            ]),
+            inherited: &InheritedLicenses(HashMap::from([(
+                Package::called("a"),
+                vec![license("root/LICENSE")],
+            )])),
+            embedded: &EmbeddedLicenses(HashMap::new()),
+            missing_notices: &MissingNotices(HashMap::from([(Package::called("a"), Vec::new())])),
+        };
+
+        assert_eq!(
+            serde_json::json!({
+                "crates": [{"name": "a", "status": "valid"}],
+                "unused": [
+                    {"name": "b", "reason": "crate not found"},
+                    {"name": "a", "reason": "skip not required", "files": ["COPYING"]}
+                ],
+                "inherited": [{"name": "a", "files": ["root/LICENSE"]}],
+                "embedded": [],
+                "missing_notices": []
+            }),
+            serde_json::to_value(&report).unwrap()
+        );
+        assert!(toml::to_string_pretty(&report).is_ok());
+    }
 }
@@ -1,15 +1,28 @@
-use crate::SummaryArgs;
 use crate::cargo_metadata::Package;
 use crate::config::Config;
 use crate::file_io::FileIO;
 use crate::licenses::check::check_licenses;
-use crate::licenses::collect::collect_licenses;
-use crate::licenses::copy::copy_licenses;
+use crate::licenses::collect::{Discovery, collect_licenses};
+use crate::licenses::content::ContentScan;
+use crate::licenses::copy::{copy_embedded_licenses, copy_licenses};
+use crate::licenses::copyright::{copyrights_by_name, find_copyrights};
 use crate::licenses::diff::diff_licenses;
-use crate::licenses::summarise::{crates_per_license, summarise};
+use crate::licenses::embedded::{EmbeddedLicenses, find_embedded_licenses};
+use crate::licenses::explain::explain_package;
+use crate::licenses::inherited::find_inherited_licenses;
+use crate::licenses::notice_files::{find_missing_notices, find_notice_files, with_notice_files};
+use crate::licenses::notices::aggregate_notices;
+use crate::licenses::prefer::{select_preferred_licenses, with_preferred_licenses};
+use crate::licenses::status::CheckReport;
+use crate::licenses::summarise::{
+    copyrights_per_license, crates_per_license, summarise, summarise_copyrights,
+};
+use crate::licenses::texts::LicenseTexts;
 use crate::licenses::unused::find_unused_configs;
 use crate::log::progress_bar;
+use crate::{CheckArgs, SummaryArgs};
 use anyhow::Context;
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::ExitCode;
 
@@ -23,20 +36,102 @@ pub fn collect(
 
     create_output_folder(path)?;
 
-    let all_licenses = collect_licenses(file_io, filtered_packages, &config.crate_configs)?;
+    let license_texts = LicenseTexts::load(file_io, config.global.license_texts.as_deref())?;
+    let all_licenses = collect_licenses(
+        file_io,
+        filtered_packages,
+        &config.crate_configs,
+        &discovery(config, &license_texts),
+    )?;
 
-    let statuses = check_licenses(file_io, &progress_bar, &all_licenses, &config.crate_configs);
+    let statuses = check_licenses(
+        file_io,
+        &progress_bar,
+        &license_texts,
+        config.confidence_threshold(),
+        &all_licenses,
+        &config.crate_configs,
+        config.global.scan_headers,
+    );
 
-    if statuses.any_invalid() {
-        print!("{statuses}");
-    }
+    print!("{statuses}");
+    print!("{}", find_inherited_licenses(&all_licenses));
 
-    copy_licenses(file_io, all_licenses, path, &config.crate_configs)?;
+    let embedded_licenses = embedded_licenses(file_io, config, filtered_packages)?;
+    print!("{embedded_licenses}");
+    print!(
+        "{}",
+        find_missing_notices(
+            file_io,
+            filtered_packages,
+            &config.global.prefer,
+            &config.crate_configs
+        )?
+    );
+
+    let selected_licenses = select_preferred_licenses(
+        file_io,
+        &license_texts,
+        config.confidence_threshold(),
+        all_licenses,
+        &config.global.prefer,
+        &config.crate_configs,
+    );
+
+    if config.global.collect_embedded {
+        copy_embedded_licenses(file_io, &embedded_licenses, path)?;
+    }
+    copy_licenses(
+        file_io,
+        with_notice_files(
+            selected_licenses,
+            find_notice_files(file_io, filtered_packages, &config.crate_configs)?,
+        ),
+        path,
+        &config.crate_configs,
+    )?;
     Ok(())
 }
 
-pub fn summary(filtered_packages: Vec<Package>, args: &SummaryArgs) -> anyhow::Result<()> {
-    let crates_per_license = crates_per_license(filtered_packages);
+pub fn summary(
+    file_io: &impl FileIO,
+    config: &Config,
+    filtered_packages: Vec<Package>,
+    args: &SummaryArgs,
+) -> anyhow::Result<()> {
+    let copyrights = if args.copyright {
+        let license_texts = LicenseTexts::load(file_io, config.global.license_texts.as_deref())?;
+        let all_licenses = collect_licenses(
+            file_io,
+            &filtered_packages,
+            &config.crate_configs,
+            &discovery(config, &license_texts),
+        )?;
+        Some(copyrights_by_name(find_copyrights(file_io, &all_licenses)?))
+    } else {
+        None
+    };
+
+    let crates_per_license = crates_per_license(with_preferred_licenses(
+        filtered_packages,
+        &config.global.prefer,
+        &config.crate_configs,
+    ));
+
+    if let Some(copyrights) = copyrights {
+        let copyrights_per_license = copyrights_per_license(crates_per_license, &copyrights);
+        println!(
+            "{}",
+            if args.json {
+                serde_json::to_string_pretty(&copyrights_per_license)?
+            } else if args.toml {
+                toml::to_string_pretty(&copyrights_per_license)?
+            } else {
+                summarise_copyrights(copyrights_per_license)
+            }
+        );
+        return Ok(());
+    }
 
     println!(
         "{}",
@@ -57,20 +152,70 @@ pub fn check(
     file_io: &impl FileIO,
     config: &Config,
     filtered_packages: &[Package],
+    args: &CheckArgs,
 ) -> anyhow::Result<ExitCode> {
     let progress_bar = progress_bar("checking licenses");
 
-    let all_licenses = collect_licenses(file_io, filtered_packages, &config.crate_configs)?;
+    let license_texts = LicenseTexts::load(file_io, config.global.license_texts.as_deref())?;
+    let all_licenses = collect_licenses(
+        file_io,
+        filtered_packages,
+        &config.crate_configs,
+        &discovery(config, &license_texts),
+    )?;
+
+    let statuses = check_licenses(
+        file_io,
+        &progress_bar,
+        &license_texts,
+        config.confidence_threshold(),
+        &all_licenses,
+        &config.crate_configs,
+        config.global.scan_headers,
+    );
 
-    let statuses = check_licenses(file_io, &progress_bar, &all_licenses, &config.crate_configs);
+    let unused = find_unused_configs(
+        file_io,
+        &license_texts,
+        config.confidence_threshold(),
+        &all_licenses,
+        &config.crate_configs,
+        config.global.scan_headers,
+    )?;
+    let inherited = find_inherited_licenses(&all_licenses);
+    let embedded = embedded_licenses(file_io, config, filtered_packages)?;
+    let missing_notices = find_missing_notices(
+        file_io,
+        filtered_packages,
+        &config.global.prefer,
+        &config.crate_configs,
+    )?;
 
-    let unused = find_unused_configs(file_io, &all_licenses, &config.crate_configs)?;
-    if unused.any() {
-        print!("{unused}");
+    // clap should make it impossible for both to be true
+    if args.json || args.toml {
+        let report = CheckReport {
+            statuses: &statuses,
+            unused: &unused,
+            inherited: &inherited,
+            embedded: &embedded,
+            missing_notices: &missing_notices,
+        };
+        if args.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            println!("{}", toml::to_string_pretty(&report)?);
+        }
+    } else {
+        if unused.any() {
+            print!("{unused}");
+        }
+        print!("{statuses}");
+        print!("{inherited}");
+        print!("{embedded}");
+        print!("{missing_notices}");
     }
 
     if statuses.any_invalid() {
-        print!("{statuses}");
         return Ok(ExitCode::FAILURE);
     }
 
@@ -83,11 +228,33 @@ pub fn diff(
     filtered_packages: &[Package],
     path: &Path,
 ) -> anyhow::Result<ExitCode> {
+    let license_texts = LicenseTexts::load(file_io, config.global.license_texts.as_deref())?;
+
     let diff = diff_licenses(
         file_io,
         path,
         &config.crate_configs,
-        collect_licenses(file_io, filtered_packages, &config.crate_configs)?,
+        with_notice_files(
+            select_preferred_licenses(
+                file_io,
+                &license_texts,
+                config.confidence_threshold(),
+                collect_licenses(
+                    file_io,
+                    filtered_packages,
+                    &config.crate_configs,
+                    &discovery(config, &license_texts),
+                )?,
+                &config.global.prefer,
+                &config.crate_configs,
+            ),
+            find_notice_files(file_io, filtered_packages, &config.crate_configs)?,
+        ),
+        &if config.global.collect_embedded {
+            find_embedded_licenses(file_io, filtered_packages, &config.crate_configs)?
+        } else {
+            EmbeddedLicenses(HashMap::new())
+        },
     )?;
 
     if !diff.is_empty() {
@@ -98,7 +265,101 @@ pub fn diff(
     Ok(ExitCode::SUCCESS)
 }
 
+pub fn notices(
+    file_io: &impl FileIO,
+    config: &Config,
+    filtered_packages: &[Package],
+) -> anyhow::Result<()> {
+    let license_texts = LicenseTexts::load(file_io, config.global.license_texts.as_deref())?;
+
+    let selected_licenses = with_notice_files(
+        select_preferred_licenses(
+            file_io,
+            &license_texts,
+            config.confidence_threshold(),
+            collect_licenses(
+                file_io,
+                filtered_packages,
+                &config.crate_configs,
+                &discovery(config, &license_texts),
+            )?,
+            &config.global.prefer,
+            &config.crate_configs,
+        ),
+        find_notice_files(file_io, filtered_packages, &config.crate_configs)?,
+    );
+
+    print!(
+        "{}",
+        aggregate_notices(
+            file_io,
+            &selected_licenses,
+            &config.global.prefer,
+            &config.crate_configs
+        )?
+    );
+    Ok(())
+}
+
 fn create_output_folder(path: &Path) -> anyhow::Result<()> {
     let _ = std::fs::remove_dir_all(path);
     std::fs::create_dir_all(path).context("failed to create output folder")
 }
+
+pub fn explain(
+    file_io: &impl FileIO,
+    config: &Config,
+    filtered_packages: &[Package],
+    crate_name: &str,
+) -> anyhow::Result<()> {
+    let normalised_name = crate_name.replace('-', "_");
+    let packages: Vec<&Package> = filtered_packages
+        .iter()
+        .filter(|package| package.normalised_name == normalised_name)
+        .collect();
+    if packages.is_empty() {
+        anyhow::bail!("crate {crate_name} not found in dependencies");
+    }
+
+    let license_texts = LicenseTexts::load(file_io, config.global.license_texts.as_deref())?;
+
+    for package in packages {
+        print!(
+            "{}",
+            explain_package(
+                file_io,
+                &license_texts,
+                config.confidence_threshold(),
+                package,
+                &config.crate_configs,
+                &discovery(config, &license_texts),
+                config.global.scan_headers,
+            )?
+        );
+    }
+
+    Ok(())
+}
+
+fn embedded_licenses(
+    file_io: &impl FileIO,
+    config: &Config,
+    filtered_packages: &[Package],
+) -> anyhow::Result<EmbeddedLicenses> {
+    if config.global.scan_embedded || config.global.collect_embedded {
+        find_embedded_licenses(file_io, filtered_packages, &config.crate_configs)
+    } else {
+        Ok(EmbeddedLicenses(HashMap::new()))
+    }
+}
+
+fn discovery<'a>(config: &Config, license_texts: &'a LicenseTexts) -> Discovery<'a> {
+    Discovery {
+        content_scan: config.global.scan_content.then(|| ContentScan {
+            license_texts,
+            confidence_threshold: config.confidence_threshold(),
+        }),
+        license_directories: config.global.scan_license_dirs,
+        sibling_versions: config.global.sibling_versions,
+    }
+}
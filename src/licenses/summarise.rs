@@ -1,8 +1,9 @@
 use crate::cargo_metadata::Package;
 use crate::licenses::License;
+use crate::licenses::copyright::Copyright;
 use colored::Colorize;
 use itertools::Itertools;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 pub fn crates_per_license(filtered_packages: Vec<Package>) -> HashMap<License, Vec<String>> {
     filtered_packages
@@ -31,11 +32,56 @@ pub fn summarise(crates_per_license: HashMap<License, Vec<String>>) -> String {
         .join("\n")
 }
 
+pub fn copyrights_per_license(
+    crates_per_license: HashMap<License, Vec<String>>,
+    copyrights: &HashMap<String, Copyright>,
+) -> HashMap<License, BTreeMap<String, Copyright>> {
+    crates_per_license
+        .into_iter()
+        .map(|(license, normalised_names)| {
+            let copyrights = normalised_names
+                .into_iter()
+                .map(|name| {
+                    let copyright = copyrights.get(&name).cloned().unwrap_or_default();
+                    (name, copyright)
+                })
+                .collect();
+            (license, copyrights)
+        })
+        .collect()
+}
+
+pub fn summarise_copyrights(
+    copyrights_per_license: HashMap<License, BTreeMap<String, Copyright>>,
+) -> String {
+    copyrights_per_license
+        .into_iter()
+        .map(|(license, copyrights)| {
+            let crates = copyrights
+                .iter()
+                .filter(|(_, copyright)| !copyright.is_empty())
+                .map(|(name, copyright)| format!("\t{name} - {copyright}"));
+            std::iter::once(format!(
+                "{} - {}",
+                license.to_string().bold(),
+                copyrights.keys().join(",").dimmed()
+            ))
+            .chain(crates)
+            .join("\n")
+        })
+        .sorted()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use crate::cargo_metadata::Package;
-    use crate::licenses::summarise::{crates_per_license, summarise};
+    use crate::licenses::copyright::Copyright;
+    use crate::licenses::summarise::{
+        copyrights_per_license, crates_per_license, summarise, summarise_copyrights,
+    };
     use cargo_metadata::camino::Utf8PathBuf;
+    use std::collections::HashMap;
 
     #[test]
     fn no_packages() {
@@ -50,6 +96,8 @@ mod tests {
                 path: Utf8PathBuf::new(),
                 url: None,
                 license: None,
+                authors: Vec::new(),
+                source: None,
             }]))
             .is_empty()
         );
@@ -64,6 +112,8 @@ mod tests {
                 path: Utf8PathBuf::new(),
                 url: None,
                 license: Some("MIT".to_string()),
+                authors: Vec::new(),
+                source: None,
             }])))
         );
     }
@@ -78,12 +128,16 @@ mod tests {
                     path: Utf8PathBuf::new(),
                     url: None,
                     license: Some("MIT".to_string()),
+                    authors: Vec::new(),
+                    source: None,
                 },
                 Package {
                     normalised_name: "another".to_string(),
                     path: Utf8PathBuf::new(),
                     url: None,
                     license: Some("Apache-2.0".to_string()),
+                    authors: Vec::new(),
+                    source: None,
                 }
             ])))
         );
@@ -99,18 +153,24 @@ mod tests {
                     path: Utf8PathBuf::new(),
                     url: None,
                     license: Some("MIT".to_string()),
+                    authors: Vec::new(),
+                    source: None,
                 },
                 Package {
                     normalised_name: "a".to_string(),
                     path: Utf8PathBuf::new(),
                     url: None,
                     license: Some("MIT".to_string()),
+                    authors: Vec::new(),
+                    source: None,
                 },
                 Package {
                     normalised_name: "b".to_string(),
                     path: Utf8PathBuf::new(),
                     url: None,
                     license: Some("MIT".to_string()),
+                    authors: Vec::new(),
+                    source: None,
                 }
             ])))
         );
@@ -126,14 +186,62 @@ mod tests {
                     path: Utf8PathBuf::from("/some/version/path/1"),
                     url: None,
                     license: Some("MIT".to_string()),
+                    authors: Vec::new(),
+                    source: None,
                 },
                 Package {
                     normalised_name: "example".to_string(),
                     path: Utf8PathBuf::from("/some/version/path/2"),
                     url: None,
                     license: Some("MIT".to_string()),
+                    authors: Vec::new(),
+                    source: None,
                 }
             ])))
         );
     }
+
+    #[test]
+    fn copyrights_are_summarised_per_crate() {
+        let packages = vec![
+            Package {
+                license: Some("MIT".to_string()),
+                ..Package::called("example")
+            },
+            Package {
+                license: Some("MIT".to_string()),
+                ..Package::called("another")
+            },
+            Package {
+                license: Some("MIT".to_string()),
+                ..Package::called("unknown")
+            },
+        ];
+        let copyrights = HashMap::from([
+            (
+                "example".to_string(),
+                Copyright {
+                    statements: vec!["Copyright (c) 2024 Example".to_string()],
+                    authors: Vec::new(),
+                },
+            ),
+            (
+                "another".to_string(),
+                Copyright {
+                    statements: Vec::new(),
+                    authors: vec!["Another <another@example.com>".to_string()],
+                },
+            ),
+        ]);
+
+        let summary = strip_ansi_escapes::strip_str(summarise_copyrights(copyrights_per_license(
+            crates_per_license(packages),
+            &copyrights,
+        )));
+
+        assert!(summary.starts_with("MIT - another,example,unknown\n"));
+        assert!(summary.contains("another - authors: Another <another@example.com>\n"));
+        assert!(summary.ends_with("example - Copyright (c) 2024 Example"));
+        assert!(!summary.contains("unknown - "));
+    }
 }
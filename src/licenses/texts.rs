@@ -0,0 +1,249 @@
+use crate::file_io::FileIO;
+use serde::Serialize;
+use spdx::detection::{Store, TextData};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{LazyLock, OnceLock};
+
+pub static LICENSE_TEXTS: LazyLock<HashMap<&'static str, &'static str>> =
+    LazyLock::new(|| spdx::text::LICENSE_TEXTS.iter().copied().collect());
+
+pub static EXCEPTION_TEXTS: LazyLock<HashMap<&'static str, &'static str>> =
+    LazyLock::new(|| spdx::text::EXCEPTION_TEXTS.iter().copied().collect());
+
+const LICENSE_TEXT_EXTENSION: &str = "txt";
+
+static BUNDLED_STORE: LazyLock<Store> = LazyLock::new(|| {
+    let mut store = Store::new();
+    for (&id, &text) in LICENSE_TEXTS.iter() {
+        store.add_license(id.to_string(), TextData::new(text));
+    }
+    store
+});
+
+pub struct LicenseTexts {
+    texts: HashMap<String, Cow<'static, str>>,
+    additional_store: OnceLock<Store>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Identified {
+    pub id: String,
+    pub score: f32,
+}
+
+impl Default for LicenseTexts {
+    fn default() -> Self {
+        Self {
+            texts: LICENSE_TEXTS
+                .iter()
+                .map(|(&id, &text)| (id.to_string(), Cow::Borrowed(text)))
+                .collect(),
+            additional_store: OnceLock::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl LicenseTexts {
+    pub fn with_text(mut self, id: &str, text: &str) -> Self {
+        self.texts
+            .insert(id.to_string(), Cow::Owned(text.to_string()));
+        self
+    }
+}
+
+impl LicenseTexts {
+    pub fn load(file_io: &impl FileIO, directory: Option<&Path>) -> anyhow::Result<Self> {
+        let mut license_texts = Self::default();
+        if let Some(directory) = directory {
+            license_texts.extend_from_directory(file_io, directory)?;
+        }
+        Ok(license_texts)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.texts.get(id).map(AsRef::as_ref)
+    }
+
+    pub fn identify(&self, text_data: &TextData) -> Option<Identified> {
+        let overrides_bundled = self.texts.iter().any(|(id, text)| {
+            matches!(text, Cow::Owned(_)) && LICENSE_TEXTS.contains_key(id.as_str())
+        });
+        let additional_store = self.additional_store.get_or_init(|| {
+            let mut store = Store::new();
+            for (id, text) in &self.texts {
+                if overrides_bundled || matches!(text, Cow::Owned(_)) {
+                    store.add_license(id.clone(), TextData::new(text));
+                }
+            }
+            store
+        });
+
+        (!overrides_bundled)
+            .then_some(&*BUNDLED_STORE)
+            .into_iter()
+            .chain([additional_store])
+            .filter(|store| !store.is_empty())
+            .map(|store| store.analyze(text_data))
+            .max_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal))
+            .map(|top_match| Identified {
+                id: top_match.name.to_string(),
+                score: top_match.score,
+            })
+    }
+
+    fn extend_from_directory(
+        &mut self,
+        file_io: &impl FileIO,
+        directory: &Path,
+    ) -> anyhow::Result<()> {
+        for dir_entry in file_io.read_dir(directory)? {
+            if !dir_entry.is_file {
+                continue;
+            }
+            let Some(id) = license_id_from_file_name(&dir_entry.path) else {
+                continue;
+            };
+            self.texts
+                .insert(id, Cow::Owned(file_io.read_file(&dir_entry.path)?));
+        }
+        Ok(())
+    }
+}
+
+fn license_id_from_file_name(path: &Path) -> Option<String> {
+    if path.extension()? != LICENSE_TEXT_EXTENSION {
+        return None;
+    }
+    path.file_stem()?.to_str().map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::{DirEntry, FileIOSpy};
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    #[test]
+    fn bundled_texts_are_available_without_a_directory() {
+        let file_io_spy = FileIOSpy::default();
+
+        let license_texts = LicenseTexts::load(&file_io_spy, None).unwrap();
+
+        assert_eq!(LICENSE_TEXTS.get("MIT").copied(), license_texts.get("MIT"));
+        assert!(file_io_spy.read_dir.arguments.take().is_empty());
+    }
+
+    #[test]
+    fn failure_to_read_directory_causes_error() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_dir
+            .returns
+            .set([Err(anyhow::anyhow!("deliberate test error"))]);
+
+        assert!(LicenseTexts::load(&file_io_spy, Some(Path::new("texts"))).is_err());
+    }
+
+    #[test]
+    fn texts_in_directory_extend_bundled_texts() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set([Ok(vec![DirEntry {
+            name: OsString::from("LicenseRef-Proprietary.txt"),
+            path: PathBuf::from("texts/LicenseRef-Proprietary.txt"),
+            is_file: true,
+        }])]);
+        file_io_spy
+            .read_file
+            .returns
+            .set([Ok("proprietary text".to_string())]);
+
+        let license_texts = LicenseTexts::load(&file_io_spy, Some(Path::new("texts"))).unwrap();
+
+        assert_eq!(
+            Some("proprietary text"),
+            license_texts.get("LicenseRef-Proprietary")
+        );
+        assert!(license_texts.get("Apache-2.0").is_some());
+    }
+
+    #[test]
+    fn texts_in_directory_override_bundled_texts() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set([Ok(vec![DirEntry {
+            name: OsString::from("MIT.txt"),
+            path: PathBuf::from("texts/MIT.txt"),
+            is_file: true,
+        }])]);
+        file_io_spy
+            .read_file
+            .returns
+            .set([Ok("organisation MIT text".to_string())]);
+
+        let license_texts = LicenseTexts::load(&file_io_spy, Some(Path::new("texts"))).unwrap();
+
+        assert_eq!(Some("organisation MIT text"), license_texts.get("MIT"));
+    }
+
+    #[test]
+    fn directories_and_files_without_txt_extension_are_ignored() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set([Ok(vec![
+            DirEntry {
+                name: OsString::from("README.md"),
+                path: PathBuf::from("texts/README.md"),
+                is_file: true,
+            },
+            DirEntry {
+                name: OsString::from("nested.txt"),
+                path: PathBuf::from("texts/nested.txt"),
+                is_file: false,
+            },
+        ])]);
+
+        let license_texts = LicenseTexts::load(&file_io_spy, Some(Path::new("texts"))).unwrap();
+
+        assert!(license_texts.get("README").is_none());
+        assert!(license_texts.get("nested").is_none());
+        assert!(file_io_spy.read_file.arguments.take().is_empty());
+    }
+
+    #[test]
+    fn identifies_text_against_all_license_texts() {
+        let identified = LicenseTexts::default()
+            .identify(&TextData::new(LICENSE_TEXTS["Unlicense"]))
+            .unwrap();
+
+        assert_eq!("Unlicense", identified.id);
+        assert!(identified.score > 0.99);
+    }
+
+    #[test]
+    fn identifies_text_against_additional_license_texts() {
+        let license_texts =
+            LicenseTexts::default().with_text("LicenseRef-Proprietary", "all rights reserved");
+
+        assert_eq!(
+            "LicenseRef-Proprietary",
+            license_texts
+                .identify(&TextData::new("all rights reserved"))
+                .unwrap()
+                .id
+        );
+    }
+
+    #[test]
+    fn overridden_bundled_texts_are_not_identified() {
+        let license_texts = LicenseTexts::default().with_text("MIT", "organisation MIT text");
+
+        let identified = license_texts
+            .identify(&TextData::new(LICENSE_TEXTS["MIT"]))
+            .unwrap();
+
+        assert_ne!("MIT", identified.id);
+    }
+}
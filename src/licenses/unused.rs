@@ -1,18 +1,21 @@
 use crate::cargo_metadata::Package;
 use crate::config::CrateConfig;
 use crate::file_io::{DirEntry, FileIO};
-use crate::licenses::License;
-use crate::licenses::is_license::is_license;
-use crate::licenses::status::LicenseStatus;
-use crate::licenses::validate::validate_licenses;
+use crate::licenses::check::package_status;
+use crate::licenses::collect::license_directory_files;
+use crate::licenses::is_license::{is_license, is_license_directory};
+use crate::licenses::notice_files::is_notice_file;
+use crate::licenses::texts::LicenseTexts;
+use crate::licenses::validate::ConfidenceThreshold;
 use crate::log::warning;
 use colored::Colorize;
 use itertools::Itertools;
+use serde::{Serialize, Serializer};
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
 #[cfg_attr(test, derive(Debug, PartialEq, Eq))]
-pub struct UnusedConfigs(Vec<(String, UnusedConfigReason)>);
+pub struct UnusedConfigs(pub Vec<(String, UnusedConfigReason)>);
 
 #[cfg_attr(test, derive(Debug, PartialEq, Eq))]
 pub enum UnusedConfigReason {
@@ -27,6 +30,31 @@ impl UnusedConfigs {
     }
 }
 
+#[derive(Serialize)]
+struct UnusedConfig<'a> {
+    name: &'a str,
+    reason: &'static str,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    files: &'a [String],
+}
+
+impl Serialize for UnusedConfigs {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|(crate_name, reason)| {
+            let (reason, files): (_, &[String]) = match reason {
+                UnusedConfigReason::CrateNotFound => ("crate not found", &[]),
+                UnusedConfigReason::AllowNotRequired => ("allow not required", &[]),
+                UnusedConfigReason::SkipNotRequired(files) => ("skip not required", files),
+            };
+            UnusedConfig {
+                name: crate_name,
+                reason,
+                files,
+            }
+        }))
+    }
+}
+
 impl Display for UnusedConfigs {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if self.0.is_empty() {
@@ -61,8 +89,11 @@ impl Display for UnusedConfigs {
 
 pub fn find_unused_configs(
     file_io: &impl FileIO,
+    license_texts: &LicenseTexts,
+    confidence_threshold: ConfidenceThreshold,
     all_licenses: &HashMap<Package, Vec<DirEntry>>,
     crate_configs: &HashMap<String, CrateConfig>,
+    scan_headers: bool,
 ) -> anyhow::Result<UnusedConfigs> {
     let package_map: HashMap<&str, (&Package, &Vec<DirEntry>)> = all_licenses
         .iter()
@@ -75,9 +106,12 @@ pub fn find_unused_configs(
         .map(|(crate_name, config)| {
             find_unused_for_crate(
                 file_io,
+                license_texts,
+                confidence_threshold,
                 crate_name,
                 config,
                 package_map.get(crate_name.as_str()),
+                scan_headers,
             )
         })
         .collect::<anyhow::Result<Vec<_>>>()?
@@ -90,9 +124,12 @@ pub fn find_unused_configs(
 
 fn find_unused_for_crate(
     file_io: &impl FileIO,
+    license_texts: &LicenseTexts,
+    confidence_threshold: ConfidenceThreshold,
     crate_name: &str,
     config: &CrateConfig,
     package_entry: Option<&(&Package, &Vec<DirEntry>)>,
+    scan_headers: bool,
 ) -> anyhow::Result<Vec<(String, UnusedConfigReason)>> {
     let Some((package, licenses)) = package_entry else {
         return Ok(vec![(
@@ -103,7 +140,15 @@ fn find_unused_for_crate(
 
     let mut unused = Vec::new();
 
-    if let Some(reason) = check_unused_allow(file_io, config, package, licenses) {
+    if let Some(reason) = check_unused_allow(
+        file_io,
+        license_texts,
+        config.confidence_threshold.unwrap_or(confidence_threshold),
+        config,
+        package,
+        licenses,
+        scan_headers,
+    ) {
         unused.push((crate_name.to_string(), reason));
     }
 
@@ -116,17 +161,25 @@ fn find_unused_for_crate(
 
 fn check_unused_allow(
     file_io: &impl FileIO,
+    license_texts: &LicenseTexts,
+    confidence_threshold: ConfidenceThreshold,
     config: &CrateConfig,
     package: &Package,
     licenses: &[DirEntry],
+    scan_headers: bool,
 ) -> Option<UnusedConfigReason> {
     config.allow.as_ref().and_then(|_| {
-        let raw_status = validate_licenses(
+        let raw_status = package_status(
             file_io,
-            package.license.as_deref().map(License::parse).as_ref(),
+            license_texts,
+            confidence_threshold,
+            package,
             licenses,
+            scan_headers,
         );
-        (raw_status == LicenseStatus::Valid).then_some(UnusedConfigReason::AllowNotRequired)
+        raw_status
+            .is_valid()
+            .then_some(UnusedConfigReason::AllowNotRequired)
     })
 }
 
@@ -150,9 +203,17 @@ fn find_unused_skip_files(
 ) -> anyhow::Result<Vec<String>> {
     let dir_entries = file_io.read_dir(package.path.as_ref())?;
 
-    let license_files: HashSet<String> = dir_entries
+    let mut license_entries: Vec<DirEntry> = Vec::new();
+    for entry in dir_entries {
+        if is_license_directory(&entry) {
+            license_entries.extend(license_directory_files(file_io, &entry)?);
+            license_entries.push(entry);
+        } else if is_license(&entry) || is_notice_file(&entry) {
+            license_entries.push(entry);
+        }
+    }
+    let license_files: HashSet<String> = license_entries
         .iter()
-        .filter(|entry| is_license(entry))
         .filter_map(|entry| entry.name.to_str().map(std::string::ToString::to_string))
         .collect();
 
@@ -169,8 +230,9 @@ mod tests {
     use crate::config::CrateConfig;
     use crate::file_io::{DirEntry, FileIOSpy};
     use crate::licenses::status::LicenseStatus;
+    use crate::licenses::texts::{LICENSE_TEXTS, LicenseTexts};
     use crate::licenses::unused::{UnusedConfigReason, UnusedConfigs, find_unused_configs};
-    use crate::licenses::validate::LICENSE_TEXTS;
+    use crate::licenses::validate::ConfidenceThreshold;
     use cargo_metadata::camino::Utf8PathBuf;
     use std::collections::HashMap;
     use std::ffi::OsString;
@@ -179,7 +241,15 @@ mod tests {
     #[test]
     fn no_unused_configs_when_no_config() {
         let file_io_spy = FileIOSpy::default();
-        let unused = find_unused_configs(&file_io_spy, &HashMap::new(), &HashMap::new()).unwrap();
+        let unused = find_unused_configs(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &HashMap::new(),
+            &HashMap::new(),
+            false,
+        )
+        .unwrap();
         assert!(!unused.any());
     }
 
@@ -192,11 +262,20 @@ mod tests {
                 skip: vec![],
                 allow: Some(LicenseStatus::TooFew),
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
 
-        let unused = find_unused_configs(&file_io_spy, &HashMap::new(), &crate_configs).unwrap();
+        let unused = find_unused_configs(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &HashMap::new(),
+            &crate_configs,
+            false,
+        )
+        .unwrap();
         assert_eq!(
             unused.0,
             vec![(
@@ -216,6 +295,8 @@ mod tests {
             path: Utf8PathBuf::default(),
             url: None,
             license: Some("MIT".to_string()),
+            authors: Vec::new(),
+            source: None,
         };
 
         let all_licenses: HashMap<_, _> = std::iter::once((
@@ -234,11 +315,20 @@ mod tests {
                 skip: vec![],
                 allow: Some(LicenseStatus::TooFew),
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
 
-        let unused = find_unused_configs(&file_io_spy, &all_licenses, &crate_configs).unwrap();
+        let unused = find_unused_configs(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &all_licenses,
+            &crate_configs,
+            false,
+        )
+        .unwrap();
         assert_eq!(
             unused.0,
             vec![(
@@ -257,6 +347,8 @@ mod tests {
             path: Utf8PathBuf::default(),
             url: None,
             license: Some("MIT".to_string()),
+            authors: Vec::new(),
+            source: None,
         };
 
         let all_licenses: HashMap<_, _> = std::iter::once((package, vec![])).collect();
@@ -267,11 +359,20 @@ mod tests {
                 skip: vec![],
                 allow: Some(LicenseStatus::Empty),
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
 
-        let unused = find_unused_configs(&file_io_spy, &all_licenses, &crate_configs).unwrap();
+        let unused = find_unused_configs(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &all_licenses,
+            &crate_configs,
+            false,
+        )
+        .unwrap();
         assert!(!unused.any());
     }
 
@@ -300,11 +401,20 @@ mod tests {
                 skip: vec!["COPYING".to_string()],
                 allow: None,
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
 
-        let unused = find_unused_configs(&file_io_spy, &all_licenses, &crate_configs).unwrap();
+        let unused = find_unused_configs(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &all_licenses,
+            &crate_configs,
+            false,
+        )
+        .unwrap();
         assert_eq!(
             unused.0,
             vec![(
@@ -346,11 +456,60 @@ mod tests {
                 skip: vec!["COPYING".to_string()],
                 allow: None,
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
 
-        let unused = find_unused_configs(&file_io_spy, &all_licenses, &crate_configs).unwrap();
+        let unused = find_unused_configs(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &all_licenses,
+            &crate_configs,
+            false,
+        )
+        .unwrap();
+        assert!(!unused.any());
+    }
+
+    #[test]
+    fn no_unused_when_skip_files_exist_in_license_directory() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set([
+            Ok(vec![DirEntry {
+                name: OsString::from("LICENSES"),
+                path: PathBuf::from("LICENSES"),
+                is_file: false,
+            }]),
+            Ok(vec![DirEntry {
+                name: OsString::from("CC0-1.0.txt"),
+                path: PathBuf::from("LICENSES/CC0-1.0.txt"),
+                is_file: true,
+            }]),
+        ]);
+
+        let all_licenses: HashMap<_, _> =
+            std::iter::once((Package::called("some_crate"), Vec::new())).collect();
+
+        let crate_configs = std::iter::once((
+            "some_crate".to_string(),
+            CrateConfig {
+                skip: vec!["LICENSES/CC0-1.0.txt".to_string()],
+                ..CrateConfig::default()
+            },
+        ))
+        .collect();
+
+        let unused = find_unused_configs(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &all_licenses,
+            &crate_configs,
+            false,
+        )
+        .unwrap();
         assert!(!unused.any());
     }
 
@@ -387,7 +546,9 @@ mod tests {
     }
 
     fn license_text(id: &str) -> String {
-        LICENSE_TEXTS.get(id).unwrap().to_string()
+        LICENSE_TEXTS[id]
+            .replace("<year> <copyright holders>", "2024 Example")
+            .replace("[yyyy] [name of copyright owner]", "2024 Example")
     }
 
     #[test]
@@ -405,6 +566,8 @@ mod tests {
             path: Utf8PathBuf::default(),
             url: None,
             license: Some("MIT".to_string()),
+            authors: Vec::new(),
+            source: None,
         };
 
         let all_licenses: HashMap<_, _> = std::iter::once((
@@ -423,11 +586,20 @@ mod tests {
                 skip: vec!["NONEXISTENT".to_string()],
                 allow: Some(LicenseStatus::TooFew),
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
 
-        let unused = find_unused_configs(&file_io_spy, &all_licenses, &crate_configs).unwrap();
+        let unused = find_unused_configs(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &all_licenses,
+            &crate_configs,
+            false,
+        )
+        .unwrap();
         assert_eq!(2, unused.0.len());
     }
 
@@ -448,15 +620,23 @@ mod tests {
                 skip: vec!["NONEXISTENT".to_string()],
                 allow: None,
                 include: vec![],
+                ..CrateConfig::default()
             },
         ))
         .collect();
 
         assert_eq!(
             "read dir failed",
-            find_unused_configs(&file_io_spy, &all_licenses, &crate_configs)
-                .unwrap_err()
-                .to_string()
+            find_unused_configs(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                &all_licenses,
+                &crate_configs,
+                false,
+            )
+            .unwrap_err()
+            .to_string()
         );
     }
 
@@ -470,6 +650,7 @@ mod tests {
                     skip: vec![],
                     allow: Some(LicenseStatus::TooFew),
                     include: vec![],
+                    ..CrateConfig::default()
                 },
             ),
             (
@@ -478,13 +659,22 @@ mod tests {
                     skip: vec![],
                     allow: Some(LicenseStatus::TooFew),
                     include: vec![],
+                    ..CrateConfig::default()
                 },
             ),
         ]
         .into_iter()
         .collect();
 
-        let unused = find_unused_configs(&file_io_spy, &HashMap::new(), &crate_configs).unwrap();
+        let unused = find_unused_configs(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            &HashMap::new(),
+            &crate_configs,
+            false,
+        )
+        .unwrap();
         assert_eq!(unused.0[0].0, "aaa_crate");
         assert_eq!(unused.0[1].0, "zzz_crate");
     }
@@ -1,18 +1,97 @@
+use crate::cargo_metadata::Package;
+use crate::config::CrateConfig;
 use crate::file_io::{DirEntry, FileIO};
 use crate::licenses::License;
-use crate::licenses::status::LicenseStatus;
+use crate::licenses::copyright::copyright_statements;
+use crate::licenses::markup::plain_text;
+use crate::licenses::modified::{VERBATIM_SCORE, differing_passages, has_additions};
+use crate::licenses::placeholders::unfilled_placeholders;
+use crate::licenses::status::{LicenseStatus, ReportedFile};
+use crate::licenses::texts::{EXCEPTION_TEXTS, Identified, LicenseTexts};
+use itertools::Itertools;
+use serde::Deserialize;
 use spdx::detection::TextData;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
 use std::sync::LazyLock;
 
-pub static LICENSE_TEXTS: LazyLock<HashMap<&'static str, &'static str>> =
-    LazyLock::new(|| spdx::text::LICENSE_TEXTS.iter().copied().collect());
+const DEFAULT_CONFIDENCE_THRESHOLD: f32 = 0.8;
 
-const CONFIDENCE_THRESHOLD: f32 = 0.8;
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(try_from = "f32")]
+pub struct ConfidenceThreshold(f32);
+
+// thresholds are always within 0.0..=1.0, so never NaN
+impl Eq for ConfidenceThreshold {}
+
+impl Default for ConfidenceThreshold {
+    fn default() -> Self {
+        Self(DEFAULT_CONFIDENCE_THRESHOLD)
+    }
+}
+
+impl TryFrom<f32> for ConfidenceThreshold {
+    type Error = String;
+
+    fn try_from(threshold: f32) -> Result<Self, Self::Error> {
+        if (0.0..=1.0).contains(&threshold) {
+            Ok(Self(threshold))
+        } else {
+            Err(format!(
+                "confidence threshold must be between 0 and 1, got {threshold}"
+            ))
+        }
+    }
+}
+
+impl FromStr for ConfidenceThreshold {
+    type Err = String;
+
+    fn from_str(threshold: &str) -> Result<Self, Self::Err> {
+        threshold
+            .parse::<f32>()
+            .map_err(|error| error.to_string())
+            .and_then(Self::try_from)
+    }
+}
+
+impl Display for ConfidenceThreshold {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ConfidenceThreshold {
+    pub fn is_met_by(self, score: f32) -> bool {
+        score >= self.0
+    }
+
+    pub fn for_package(
+        self,
+        package: &Package,
+        crate_configs: &HashMap<String, CrateConfig>,
+    ) -> Self {
+        crate_configs
+            .get(&package.normalised_name)
+            .and_then(|config| config.confidence_threshold)
+            .unwrap_or(self)
+    }
+}
+
+static EXCEPTION_TEXT_DATA: LazyLock<Vec<(&'static str, TextData)>> = LazyLock::new(|| {
+    EXCEPTION_TEXTS
+        .iter()
+        .map(|(&id, &text)| (id, TextData::new(text)))
+        .collect()
+});
 
 pub fn validate_licenses(
     file_io: &impl FileIO,
+    license_texts: &LicenseTexts,
+    confidence_threshold: ConfidenceThreshold,
     declared_licenses: Option<&License>,
     actual_licenses: &[DirEntry],
 ) -> LicenseStatus {
@@ -24,82 +103,610 @@ pub fn validate_licenses(
         return LicenseStatus::NoneDeclared;
     };
 
-    let expected_texts = expected_texts_from_declared(declared);
-    let unmatched_license_files =
-        unmatched_license_files(file_io, &expected_texts, actual_licenses);
+    let threshold = confidence_threshold.0;
+    let declared_ids = declared_license_ids(license_texts, declared);
+    let expected_texts = expected_texts_from_declared(license_texts, &declared_ids);
+    let (license_files, other_files): (Vec<LicenseFile>, Vec<LicenseFile>) = match_license_files(
+        file_io,
+        &declared_ids,
+        &expected_texts,
+        actual_licenses,
+        threshold,
+    )
+    .into_iter()
+    .partition(|file| file.id.is_some());
+    let (unreadable_files, other_files): (Vec<LicenseFile>, Vec<LicenseFile>) = other_files
+        .into_iter()
+        .partition(|file| file.error.is_some());
+    if !unreadable_files.is_empty() {
+        return LicenseStatus::Unreadable(to_unreadable_files(unreadable_files));
+    }
+    let found_ids: Vec<&str> = license_files.iter().flat_map(LicenseFile::ids).collect();
+    let (exception_files, unmatched_license_files): (Vec<_>, Vec<_>) = other_files
+        .into_iter()
+        .map(|file| (identify_exception(&file, threshold), file))
+        .partition(|(exception, _)| exception.is_some());
+    let exception_files: Vec<(Option<&str>, DirEntry)> = exception_files
+        .into_iter()
+        .map(|(exception, file)| (exception, file.entry))
+        .collect();
+    let unmatched_license_files: Vec<LicenseFile> = unmatched_license_files
+        .into_iter()
+        .map(|(_, file)| file)
+        .collect();
+    let misnamed = misnamed_files(&license_files, &declared_ids, &expected_texts);
+    let unfilled = unfilled_template_files(&license_files);
+    let modified = modified_files(declared, &license_files, &expected_texts, threshold);
 
-    if !unmatched_license_files.is_empty()
-        && !found_all_declared_licenses(declared, actual_licenses, &unmatched_license_files)
-    {
-        return LicenseStatus::Mismatch(to_file_names(unmatched_license_files));
+    if found_ids.len() == declared_ids.len() {
+        return if unmatched_license_files.is_empty() {
+            validate_exceptions(
+                declared,
+                &found_ids,
+                &license_files,
+                exception_files,
+                &expected_texts,
+                threshold,
+            )
+            .unwrap_or_else(|| {
+                with_reviewed_files(LicenseStatus::Valid, misnamed, unfilled, modified)
+            })
+        } else {
+            LicenseStatus::Additional(to_reported_files(
+                unmatched_license_files,
+                &[],
+                license_texts,
+                threshold,
+            ))
+        };
     }
 
-    match actual_licenses.len().cmp(&declared.requirements().count()) {
-        Ordering::Equal => LicenseStatus::Valid,
-        Ordering::Less => LicenseStatus::TooFew,
-        Ordering::Greater => LicenseStatus::Additional(to_file_names(unmatched_license_files)),
+    if !unmatched_license_files.is_empty() {
+        return LicenseStatus::Mismatch(to_reported_files(
+            unmatched_license_files,
+            &expected_texts,
+            license_texts,
+            threshold,
+        ));
     }
+
+    let Some(branch) = declared.satisfied_branch(|id| found_ids.contains(&id)) else {
+        return LicenseStatus::TooFew;
+    };
+    validate_exceptions(
+        declared,
+        &found_ids,
+        &license_files,
+        exception_files,
+        &expected_texts,
+        threshold,
+    )
+    .unwrap_or_else(|| {
+        with_reviewed_files(
+            LicenseStatus::Satisfied(branch),
+            misnamed,
+            unfilled,
+            modified,
+        )
+    })
+}
+
+pub fn declared_license_files(
+    file_io: &impl FileIO,
+    license_texts: &LicenseTexts,
+    confidence_threshold: ConfidenceThreshold,
+    declared: &License,
+    actual_licenses: &[DirEntry],
+) -> Vec<(DirEntry, Vec<String>)> {
+    let declared_ids = declared_license_ids(license_texts, declared);
+    let expected_texts = expected_texts_from_declared(license_texts, &declared_ids);
+    match_license_files(
+        file_io,
+        &declared_ids,
+        &expected_texts,
+        actual_licenses,
+        confidence_threshold.0,
+    )
+    .into_iter()
+    .map(|file| {
+        let ids = file.ids().map(ToString::to_string).collect();
+        (file.entry, ids)
+    })
+    .collect()
+}
+
+pub fn declared_match_scores(
+    file_io: &impl FileIO,
+    license_texts: &LicenseTexts,
+    declared: &License,
+    license: &DirEntry,
+) -> anyhow::Result<Vec<(String, f32)>> {
+    let declared_ids = declared_license_ids(license_texts, declared);
+    let text_data = TextData::from(plain_text(&file_io.read_file(&license.path)?).as_str());
+    Ok(expected_texts_from_declared(license_texts, &declared_ids)
+        .into_iter()
+        .map(|(id, expected)| (id.to_string(), text_data.match_score(&expected)))
+        .collect())
+}
+
+struct LicenseFile<'a> {
+    entry: DirEntry,
+    // the plain text keeps the brackets of template placeholders, which scoring normalises away
+    text: Option<String>,
+    text_data: Option<TextData>,
+    error: Option<String>,
+    id: Option<&'a str>,
+    contained_ids: Vec<&'a str>,
 }
 
-fn expected_texts_from_declared(declared: &License) -> Vec<TextData> {
-    declared
-        .requirements()
-        .filter_map(|expression| {
-            let id = expression.req.license.id()?;
-            LICENSE_TEXTS.get(id.name).map(|&text| TextData::new(text))
+impl<'a> LicenseFile<'a> {
+    fn ids(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.id
+            .into_iter()
+            .chain(self.contained_ids.iter().copied())
+    }
+}
+
+fn declared_license_ids(license_texts: &LicenseTexts, declared: &License) -> Vec<String> {
+    match declared {
+        License::Known(_) => declared.ids(),
+        License::Unknown(license) => license_texts
+            .get(license)
+            .map(|_| declared.ids())
+            .unwrap_or_default(),
+    }
+}
+
+fn expected_texts_from_declared<'a>(
+    license_texts: &LicenseTexts,
+    declared_ids: &'a [String],
+) -> Vec<(&'a str, TextData)> {
+    declared_ids
+        .iter()
+        .filter_map(|id| {
+            license_texts
+                .get(id)
+                .map(|text| (id.as_str(), TextData::new(text)))
         })
         .collect()
 }
 
-fn unmatched_license_files(
+fn match_license_files<'a>(
     file_io: &impl FileIO,
-    expected_texts: &[TextData],
+    declared_ids: &'a [String],
+    expected_texts: &[(&'a str, TextData)],
     actual_licenses: &[DirEntry],
-) -> Vec<DirEntry> {
-    let mut candidates: Vec<(DirEntry, Option<TextData>)> = actual_licenses
+    threshold: f32,
+) -> Vec<LicenseFile<'a>> {
+    let mut candidates: Vec<LicenseFile> = actual_licenses
         .iter()
         .map(|entry| {
-            let text_data = file_io
-                .read_file(&entry.path)
-                .ok()
-                .map(|contents| TextData::from(contents.as_str()));
-            (entry.clone(), text_data)
+            let (text, error) = match file_io.read_file(&entry.path) {
+                Ok(contents) => (Some(plain_text(&contents)), None),
+                Err(error) => (None, Some(error.root_cause().to_string())),
+            };
+            LicenseFile {
+                entry: entry.clone(),
+                text_data: text.as_deref().map(TextData::from),
+                text,
+                error,
+                id: None,
+                contained_ids: Vec::new(),
+            }
         })
         .collect();
-    candidates.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+    candidates.sort_by(|a, b| a.entry.name.cmp(&b.entry.name));
 
-    for expected in expected_texts {
-        if let Some(index) = find_matching_index(&candidates, expected) {
-            candidates.swap_remove(index);
+    let mut matched: Vec<LicenseFile> = Vec::new();
+    for id in declared_ids {
+        if let Some(index) = candidates
+            .iter()
+            .position(|file| reuse_license_id(&file.entry) == Some(id.as_str()))
+        {
+            let mut file = candidates.swap_remove(index);
+            file.id = Some(id.as_str());
+            matched.push(file);
         }
     }
 
-    candidates.into_iter().map(|(entry, _)| entry).collect()
+    for (id, expected) in expected_texts {
+        if matched.iter().any(|file| file.id == Some(*id)) {
+            continue;
+        }
+        if let Some(index) = candidates.iter().position(|file| {
+            hinted_license_id(&file.entry, declared_ids) == Some(*id)
+                && file
+                    .text_data
+                    .as_ref()
+                    .is_some_and(|text_data| text_data.match_score(expected) >= threshold)
+        }) {
+            let mut file = candidates.swap_remove(index);
+            file.id = Some(*id);
+            matched.push(file);
+        }
+    }
+
+    for (id, expected) in expected_texts {
+        if matched.iter().any(|file| file.id == Some(*id)) {
+            continue;
+        }
+        if let Some(index) = find_matching_index(&candidates, expected, threshold) {
+            let mut file = candidates.swap_remove(index);
+            file.id = Some(*id);
+            matched.push(file);
+        }
+    }
+
+    for (id, expected) in expected_texts {
+        if matched
+            .iter()
+            .any(|file| file.ids().any(|found| found == *id))
+        {
+            continue;
+        }
+        if let Some(file) = matched
+            .iter_mut()
+            .find(|file| contains_text(file.text_data.as_ref(), expected, threshold))
+        {
+            file.contained_ids.push(*id);
+        } else if let Some(index) = candidates
+            .iter()
+            .position(|file| contains_text(file.text_data.as_ref(), expected, threshold))
+        {
+            let mut file = candidates.swap_remove(index);
+            file.id = Some(*id);
+            matched.push(file);
+        }
+    }
+
+    matched.extend(candidates);
+    matched
+}
+
+fn reuse_license_id(entry: &DirEntry) -> Option<&str> {
+    let name = Path::new(&entry.name);
+    name.parent()?
+        .file_name()?
+        .eq_ignore_ascii_case("LICENSES")
+        .then(|| name.file_stem()?.to_str())
+        .flatten()
 }
 
-fn found_all_declared_licenses(
+fn hinted_license_id<'a>(entry: &DirEntry, declared_ids: &'a [String]) -> Option<&'a str> {
+    let name = Path::new(&entry.name).file_name()?.to_str()?.to_lowercase();
+    let words: Vec<&str> = name.split(|c: char| !c.is_ascii_alphanumeric()).collect();
+    declared_ids
+        .iter()
+        .filter(|id| name.contains(&id.to_lowercase()))
+        .max_by_key(|id| id.len())
+        .or_else(|| {
+            declared_ids
+                .iter()
+                .filter(|id| words.contains(&license_family(id).as_str()))
+                .exactly_one()
+                .ok()
+        })
+        .map(String::as_str)
+}
+
+fn license_family(id: &str) -> String {
+    id.split(|c: char| !c.is_ascii_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn misnamed_files(
+    license_files: &[LicenseFile],
+    declared_ids: &[String],
+    expected_texts: &[(&str, TextData)],
+) -> Vec<ReportedFile> {
+    let mut misnamed: Vec<ReportedFile> = license_files
+        .iter()
+        .filter(|file| {
+            hinted_license_id(&file.entry, declared_ids)
+                .is_some_and(|hint| !file.ids().any(|id| id == hint))
+        })
+        .map(|file| ReportedFile {
+            name: file.entry.name.to_string_lossy().to_string(),
+            score: None,
+            detected: file.id.map(|id| Identified {
+                id: id.to_string(),
+                score: file
+                    .text_data
+                    .as_ref()
+                    .zip(
+                        expected_texts
+                            .iter()
+                            .find(|(expected_id, _)| *expected_id == id),
+                    )
+                    .map_or(0.0, |(text_data, (_, expected))| {
+                        text_data.match_score(expected)
+                    }),
+            }),
+            differences: Vec::new(),
+            placeholders: Vec::new(),
+            error: None,
+        })
+        .collect();
+    misnamed.sort();
+    misnamed
+}
+
+fn modified_files(
     declared: &License,
-    actual_licenses: &[DirEntry],
-    unmatched_license_files: &[DirEntry],
-) -> bool {
-    declared.requirements().count() == actual_licenses.len() - unmatched_license_files.len()
+    license_files: &[LicenseFile],
+    expected_texts: &[(&str, TextData)],
+    threshold: f32,
+) -> Vec<ReportedFile> {
+    let declared_exceptions = declared.exceptions();
+    let mut modified: Vec<ReportedFile> = license_files
+        .iter()
+        .filter(|file| file.contained_ids.is_empty())
+        .filter(|file| {
+            !declared_exceptions
+                .iter()
+                .any(|(license, _)| Some(license.as_str()) == file.id)
+        })
+        .filter_map(|file| {
+            let text_data = file.text_data.as_ref()?;
+            let (_, expected) = expected_texts
+                .iter()
+                .find(|(expected_id, _)| Some(*expected_id) == file.id)?;
+            let score = text_data.match_score(expected);
+            if score < threshold || score >= VERBATIM_SCORE {
+                return None;
+            }
+            let differences = differing_passages(text_data, expected);
+            has_additions(&differences).then(|| ReportedFile {
+                name: file.entry.name.to_string_lossy().to_string(),
+                score: Some(score),
+                detected: None,
+                differences,
+                placeholders: Vec::new(),
+                error: None,
+            })
+        })
+        .collect();
+    modified.sort();
+    modified
+}
+
+fn unfilled_template_files(license_files: &[LicenseFile]) -> Vec<ReportedFile> {
+    let texts = license_files.iter().filter_map(|file| file.text.as_deref());
+    if texts
+        .clone()
+        .any(|text| !copyright_statements(text).is_empty())
+    {
+        return Vec::new();
+    }
+    let mut unfilled: Vec<ReportedFile> = license_files
+        .iter()
+        .filter_map(|file| {
+            let placeholders = unfilled_placeholders(file.text.as_deref()?);
+            (!placeholders.is_empty()).then(|| ReportedFile {
+                name: file.entry.name.to_string_lossy().to_string(),
+                score: None,
+                detected: None,
+                differences: Vec::new(),
+                placeholders,
+                error: None,
+            })
+        })
+        .collect();
+    unfilled.sort();
+    unfilled
+}
+
+fn with_reviewed_files(
+    license_status: LicenseStatus,
+    misnamed: Vec<ReportedFile>,
+    unfilled: Vec<ReportedFile>,
+    modified: Vec<ReportedFile>,
+) -> LicenseStatus {
+    if !misnamed.is_empty() {
+        LicenseStatus::Misnamed(misnamed)
+    } else if !unfilled.is_empty() {
+        LicenseStatus::UnfilledTemplate(unfilled)
+    } else if !modified.is_empty() {
+        LicenseStatus::Modified(modified)
+    } else {
+        license_status
+    }
 }
 
 fn find_matching_index(
-    candidates: &[(DirEntry, Option<TextData>)],
+    candidates: &[LicenseFile],
     expected: &TextData,
+    threshold: f32,
 ) -> Option<usize> {
     candidates
         .iter()
         .enumerate()
-        .filter_map(|(index, (_, text_data))| {
-            let score = text_data.as_ref()?.match_score(expected);
-            (score >= CONFIDENCE_THRESHOLD).then_some((index, score))
+        .filter_map(|(index, file)| {
+            let score = file.text_data.as_ref()?.match_score(expected);
+            (score >= threshold).then_some((index, score))
         })
         .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
         .map(|(index, _)| index)
 }
 
+fn identify_exception(file: &LicenseFile, threshold: f32) -> Option<&'static str> {
+    let text_data = file.text_data.as_ref()?;
+    EXCEPTION_TEXT_DATA
+        .iter()
+        .map(|(id, exception)| (*id, text_data.match_score(exception)))
+        .filter(|(_, score)| *score >= threshold)
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        .map(|(id, _)| id)
+}
+
+fn validate_exceptions(
+    declared: &License,
+    found_ids: &[&str],
+    license_files: &[LicenseFile],
+    exception_files: Vec<(Option<&str>, DirEntry)>,
+    expected_texts: &[(&str, TextData)],
+    threshold: f32,
+) -> Option<LicenseStatus> {
+    let declared_exceptions = declared.exceptions();
+
+    let missing_exceptions: Vec<String> = declared_exceptions
+        .iter()
+        .filter(|(license, _)| found_ids.contains(&license.as_str()))
+        .filter_map(|(_, exception)| exception_text_data(exception).map(|text| (exception, text)))
+        .filter(|(exception, text)| {
+            !exception_files
+                .iter()
+                .any(|(id, _)| *id == Some(exception.as_str()))
+                && !license_files
+                    .iter()
+                    .any(|file| contains_text(file.text_data.as_ref(), text, threshold))
+        })
+        .map(|(exception, _)| exception.clone())
+        .sorted()
+        .dedup()
+        .collect();
+    if !missing_exceptions.is_empty() {
+        return Some(LicenseStatus::MissingException(missing_exceptions));
+    }
+
+    let is_undeclared = |id: &str| {
+        !declared_exceptions
+            .iter()
+            .any(|(_, exception)| exception == id)
+    };
+    let undeclared_exception_files: Vec<DirEntry> = exception_files
+        .into_iter()
+        .filter(|(id, _)| id.is_some_and(is_undeclared))
+        .map(|(_, entry)| entry)
+        .chain(
+            license_files
+                .iter()
+                .filter(|file| {
+                    appended_exception(file, expected_texts, threshold).is_some_and(is_undeclared)
+                })
+                .map(|file| file.entry.clone()),
+        )
+        .collect();
+    (!undeclared_exception_files.is_empty())
+        .then(|| LicenseStatus::UndeclaredException(to_file_names(undeclared_exception_files)))
+}
+
+fn appended_exception(
+    file: &LicenseFile,
+    expected_texts: &[(&str, TextData)],
+    threshold: f32,
+) -> Option<&'static str> {
+    let mut remainder = file.text_data.clone()?;
+    for id in file.ids() {
+        if let Some((_, expected)) = expected_texts
+            .iter()
+            .find(|(expected_id, _)| *expected_id == id)
+        {
+            remainder = remainder.optimize_bounds(expected).0.white_out();
+        }
+    }
+    let remainder = remainder
+        .lines()
+        .iter()
+        .filter(|line| !line.is_empty())
+        .join("\n");
+    if remainder.is_empty() {
+        return None;
+    }
+    let remainder = TextData::new(&remainder);
+    EXCEPTION_TEXT_DATA
+        .iter()
+        .map(|(id, exception)| (*id, remainder.optimize_bounds(exception).1))
+        .filter(|(_, score)| *score >= threshold)
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        .map(|(id, _)| id)
+}
+
+fn exception_text_data(exception: &str) -> Option<&'static TextData> {
+    EXCEPTION_TEXT_DATA
+        .iter()
+        .find(|(id, _)| *id == exception)
+        .map(|(_, text_data)| text_data)
+}
+
+fn contains_text(text_data: Option<&TextData>, expected: &TextData, threshold: f32) -> bool {
+    let Some(text_data) = text_data else {
+        return false;
+    };
+    if text_data.match_score(expected) >= threshold {
+        return true;
+    }
+
+    let (_, end) = text_data.lines_view();
+    let window = expected.lines().len() * 3 / 2;
+    let step = (window / 4).max(1);
+    let Some(best_start) = (0..end.saturating_sub(window / 2))
+        .step_by(step)
+        .map(|start| {
+            let score = text_data
+                .with_view(start, (start + window).min(end))
+                .match_score(expected);
+            (start, score)
+        })
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        .map(|(start, _)| start)
+    else {
+        return false;
+    };
+    text_data
+        .with_view(
+            best_start.saturating_sub(step),
+            (best_start + window + step).min(end),
+        )
+        .optimize_bounds(expected)
+        .1
+        >= threshold
+}
+
+fn to_reported_files(
+    files: Vec<LicenseFile>,
+    expected_texts: &[(&str, TextData)],
+    license_texts: &LicenseTexts,
+    threshold: f32,
+) -> Vec<ReportedFile> {
+    let mut reported_files: Vec<ReportedFile> = files
+        .into_iter()
+        .map(|file| ReportedFile {
+            name: file.entry.name.to_string_lossy().to_string(),
+            score: file.text_data.as_ref().and_then(|text_data| {
+                expected_texts
+                    .iter()
+                    .map(|(_, expected)| text_data.match_score(expected))
+                    .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            }),
+            detected: file
+                .text_data
+                .as_ref()
+                .and_then(|text_data| license_texts.identify(text_data))
+                .filter(|identified| identified.score >= threshold),
+            differences: Vec::new(),
+            placeholders: Vec::new(),
+            error: None,
+        })
+        .collect();
+    reported_files.sort();
+    reported_files
+}
+
+fn to_unreadable_files(files: Vec<LicenseFile>) -> Vec<ReportedFile> {
+    let mut unreadable: Vec<ReportedFile> = files
+        .into_iter()
+        .map(|file| ReportedFile {
+            error: file.error,
+            ..ReportedFile::from(file.entry.name.to_string_lossy().to_string())
+        })
+        .collect();
+    unreadable.sort();
+    unreadable
+}
+
 fn to_file_names(entries: Vec<DirEntry>) -> Vec<String> {
     let mut names: Vec<String> = entries
         .into_iter()
@@ -113,27 +720,49 @@ fn to_file_names(entries: Vec<DirEntry>) -> Vec<String> {
 mod tests {
     use super::*;
     use crate::file_io::FileIOSpy;
+    use crate::licenses::modified::Difference;
+    use crate::licenses::texts::LICENSE_TEXTS;
     use std::ffi::OsString;
     use std::path::PathBuf;
 
     #[test]
-    fn failure_to_read_license_file_causes_mismatch() {
+    fn failure_to_read_license_file_is_unreadable() {
         let file_io_spy = FileIOSpy::default();
-        file_io_spy
-            .read_file
-            .returns
-            .set([Err(anyhow::anyhow!("deliberate test error"))]);
+        file_io_spy.read_file.returns.set([Err(
+            anyhow::anyhow!("binary file").context("failed to read file LICENSE")
+        )]);
+
+        let status = validate_licenses(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            Some(&License::parse("MIT")),
+            &license_files(&["LICENSE"]),
+        );
+
+        assert_eq!(LicenseStatus::Unreadable(vec!["LICENSE".into()]), status);
+        assert_eq!(Some("LICENSE (binary file)".to_string()), status.details());
+    }
+
+    #[test]
+    fn unreadable_files_are_reported_before_other_statuses() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            if path == Path::new("LICENSE-MIT") {
+                Ok(license_text("MIT"))
+            } else {
+                Err(anyhow::anyhow!("deliberate test error"))
+            }
+        });
 
         assert_eq!(
-            LicenseStatus::Mismatch(vec!["LICENSE".to_string()]),
+            LicenseStatus::Unreadable(vec!["LICENSE-APACHE".into()]),
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("MIT")),
-                &[DirEntry {
-                    name: OsString::from("LICENSE"),
-                    path: PathBuf::new(),
-                    is_file: true,
-                }]
+                &license_files(&["LICENSE-APACHE", "LICENSE-MIT"]),
             )
         );
     }
@@ -143,7 +772,13 @@ mod tests {
         let file_io_spy = FileIOSpy::default();
         assert_eq!(
             LicenseStatus::Empty,
-            validate_licenses(&file_io_spy, Some(&License::parse("MIT")), &[])
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("MIT")),
+                &[]
+            )
         );
     }
 
@@ -154,6 +789,8 @@ mod tests {
             LicenseStatus::NoneDeclared,
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 None,
                 &[DirEntry {
                     name: OsString::new(),
@@ -173,6 +810,29 @@ mod tests {
             LicenseStatus::TooFew,
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("MIT AND Apache-2.0")),
+                &[DirEntry {
+                    name: OsString::from("LICENSE_MIT"),
+                    path: PathBuf::new(),
+                    is_file: true,
+                }]
+            )
+        );
+    }
+
+    #[test]
+    fn single_alternative_satisfies_dual_license() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set([Ok(license_text("MIT"))]);
+
+        assert_eq!(
+            LicenseStatus::Satisfied("MIT".to_string()),
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("MIT OR Apache-2.0")),
                 &[DirEntry {
                     name: OsString::from("LICENSE_MIT"),
@@ -184,14 +844,16 @@ mod tests {
     }
 
     #[test]
-    fn too_few_licenses_non_standard_seperator() {
+    fn single_alternative_satisfies_dual_license_non_standard_seperator() {
         let file_io_spy = FileIOSpy::default();
         file_io_spy.read_file.returns.set([Ok(license_text("MIT"))]);
 
         assert_eq!(
-            LicenseStatus::TooFew,
+            LicenseStatus::Satisfied("MIT".to_string()),
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("MIT/Apache-2.0")),
                 &[DirEntry {
                     name: OsString::from("LICENSE_MIT"),
@@ -203,7 +865,7 @@ mod tests {
     }
 
     #[test]
-    fn too_few_licenses_complex_requirements() {
+    fn single_alternative_satisfies_complex_requirements() {
         let file_io_spy = FileIOSpy::default();
         file_io_spy
             .read_file
@@ -211,9 +873,11 @@ mod tests {
             .set([Ok(license_text("MIT")), Ok(license_text("Unicode-3.0"))]);
 
         assert_eq!(
-            LicenseStatus::TooFew,
+            LicenseStatus::Satisfied("MIT AND Unicode-3.0".to_string()),
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("(MIT OR Apache-2.0) AND Unicode-3.0")),
                 &[
                     DirEntry {
@@ -231,6 +895,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn too_few_licenses_complex_requirements() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set([Ok(license_text("MIT"))]);
+
+        assert_eq!(
+            LicenseStatus::TooFew,
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("(MIT OR Apache-2.0) AND Unicode-3.0")),
+                &[DirEntry {
+                    name: OsString::from("LICENSE_MIT"),
+                    path: PathBuf::new(),
+                    is_file: true,
+                }]
+            )
+        );
+    }
+
     #[test]
     fn additional_licenses() {
         let file_io_spy = FileIOSpy::default();
@@ -240,9 +925,11 @@ mod tests {
             .set([Ok(license_text("MIT")), Ok("not a license".to_string())]);
 
         assert_eq!(
-            LicenseStatus::Additional(vec!["COPYING".to_string()]),
+            LicenseStatus::Additional(vec!["COPYING".into()]),
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("MIT")),
                 &[
                     DirEntry {
@@ -269,9 +956,11 @@ mod tests {
             .set([Ok(license_text("Apache-2.0"))]);
 
         assert_eq!(
-            LicenseStatus::Mismatch(vec!["LICENSE_MIT".to_string()]),
+            LicenseStatus::Mismatch(vec!["LICENSE_MIT".into()]),
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("MIT")),
                 &[DirEntry {
                     name: OsString::from("LICENSE_MIT"),
@@ -291,6 +980,8 @@ mod tests {
             LicenseStatus::Valid,
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("MIT")),
                 &[DirEntry {
                     name: OsString::from("LICENSE"),
@@ -318,6 +1009,8 @@ mod tests {
             LicenseStatus::Valid,
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("MIT")),
                 &[DirEntry {
                     name: OsString::from("LICENSE"),
@@ -339,9 +1032,11 @@ mod tests {
         file_io_spy.read_file.returns.set([Ok(combined)]);
 
         assert_eq!(
-            LicenseStatus::TooFew,
+            LicenseStatus::Valid,
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("MIT OR Apache-2.0")),
                 &[DirEntry {
                     name: OsString::from("LICENSE"),
@@ -353,7 +1048,68 @@ mod tests {
     }
 
     fn license_text(id: &str) -> String {
-        LICENSE_TEXTS.get(id).unwrap().to_string()
+        LICENSE_TEXTS[id]
+            .replace("<year> <copyright holders>", "2024 Example")
+            .replace("[yyyy] [name of copyright owner]", "2024 Example")
+    }
+
+    #[test]
+    fn license_ref_validated_against_license_texts() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set([Ok(license_text("MIT")), Ok(license_text("MIT"))]);
+
+        let entries = [DirEntry {
+            name: OsString::from("LICENSE"),
+            path: PathBuf::new(),
+            is_file: true,
+        }];
+
+        assert_eq!(
+            LicenseStatus::Mismatch(vec!["LICENSE".into()]),
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default()
+                    .with_text("LicenseRef-Proprietary", &license_text("Apache-2.0")),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("LicenseRef-Proprietary")),
+                &entries
+            )
+        );
+
+        assert_eq!(
+            LicenseStatus::Valid,
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default().with_text("LicenseRef-Proprietary", &license_text("MIT")),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("LicenseRef-Proprietary")),
+                &entries
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_declared_license_validated_when_in_license_texts() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set([Ok(license_text("MIT"))]);
+
+        assert_eq!(
+            LicenseStatus::Valid,
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default().with_text("Newer-License-1.0", &license_text("MIT")),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("Newer-License-1.0")),
+                &[DirEntry {
+                    name: OsString::from("LICENSE"),
+                    path: PathBuf::new(),
+                    is_file: true,
+                }]
+            )
+        );
     }
 
     #[test]
@@ -368,6 +1124,8 @@ mod tests {
             LicenseStatus::Valid,
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("MIT OR Apache-2.0")),
                 &[
                     DirEntry {
@@ -394,9 +1152,11 @@ mod tests {
             .set([Ok("anything".to_string())]);
 
         assert_eq!(
-            LicenseStatus::Additional(vec!["LICENSE".to_string()]),
+            LicenseStatus::Additional(vec!["LICENSE".into()]),
             validate_licenses(
                 &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
                 Some(&License::parse("not-a-real-license")),
                 &[DirEntry {
                     name: OsString::from("LICENSE"),
@@ -450,9 +1210,11 @@ mod tests {
             ));
 
             assert_eq!(
-                LicenseStatus::Additional(vec!["LICENSE-THIRD-PARTY".to_string()]),
+                LicenseStatus::Additional(vec!["LICENSE-THIRD-PARTY".into()]),
                 validate_licenses(
                     &file_io_spy,
+                    &LicenseTexts::default(),
+                    ConfidenceThreshold::default(),
                     Some(&License::parse("MIT OR Apache-2.0")),
                     &entries_in_order(order),
                 ),
@@ -483,10 +1245,544 @@ mod tests {
                 .collect::<Vec<_>>();
 
             assert_eq!(
-                LicenseStatus::Additional(vec!["LICENSE".to_string()]),
-                validate_licenses(&file_io_spy, Some(&License::parse("MIT")), &entries),
+                LicenseStatus::Additional(vec!["LICENSE".into()]),
+                validate_licenses(
+                    &file_io_spy,
+                    &LicenseTexts::default(),
+                    ConfidenceThreshold::default(),
+                    Some(&License::parse("MIT")),
+                    &entries
+                ),
                 "input order {order:?} produced the wrong additional file"
             );
         }
     }
+
+    fn exception_text(id: &str) -> String {
+        EXCEPTION_TEXTS.get(id).unwrap().to_string()
+    }
+
+    fn license_files(names: &[&str]) -> Vec<DirEntry> {
+        names
+            .iter()
+            .map(|name| DirEntry {
+                name: OsString::from(name),
+                path: PathBuf::from(name),
+                is_file: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reuse_license_files_are_matched_by_name() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_: &PathBuf| Ok("reformatted beyond recognition".to_string()));
+
+        assert_eq!(
+            LicenseStatus::Valid,
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("MIT OR Apache-2.0")),
+                &license_files(&["LICENSES/Apache-2.0.txt", "LICENSES/MIT.txt"]),
+            )
+        );
+    }
+
+    #[test]
+    fn license_files_wrapped_in_markup_are_matched() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            let paragraphs = license_text("MIT");
+            let paragraphs = paragraphs.split("\n\n");
+            Ok(if path.ends_with("LICENSE.html") {
+                format!(
+                    "<html><head><style>p {{ margin: 0; }}</style></head><body>{}</body></html>",
+                    paragraphs
+                        .map(|paragraph| format!("<p class=\"license-text\">{paragraph}</p>"))
+                        .join("\n")
+                )
+            } else {
+                paragraphs
+                    .flat_map(|paragraph| paragraph.lines().chain(std::iter::once("")))
+                    .map(|line| format!("// {line}"))
+                    .join("\n")
+            })
+        });
+
+        for name in ["LICENSE.html", "LICENSE.rs"] {
+            assert_eq!(
+                LicenseStatus::Valid,
+                validate_licenses(
+                    &file_io_spy,
+                    &LicenseTexts::default(),
+                    ConfidenceThreshold::default(),
+                    Some(&License::parse("MIT")),
+                    &license_files(&[name]),
+                ),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn license_files_adding_to_the_reference_text_are_modified() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|_: &PathBuf| {
+            Ok(license_text("MIT").replace(
+                "free of charge,",
+                "free of charge, for non-commercial use only,",
+            ))
+        });
+
+        let status = validate_licenses(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            Some(&License::parse("MIT")),
+            &license_files(&["LICENSE"]),
+        );
+
+        let LicenseStatus::Modified(files) = &status else {
+            panic!("expected modified, got {status:?}");
+        };
+        assert_eq!(
+            vec![Difference::Added("for non-commercial use only".to_string())],
+            files[0].differences
+        );
+        assert!(status.is_valid());
+    }
+
+    #[test]
+    fn license_files_omitting_from_the_reference_text_are_not_modified() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|_: &PathBuf| {
+            Ok(license_text("Apache-2.0")
+                .split("APPENDIX")
+                .next()
+                .unwrap()
+                .to_string())
+        });
+
+        assert_eq!(
+            LicenseStatus::Valid,
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("Apache-2.0")),
+                &license_files(&["LICENSE"]),
+            )
+        );
+    }
+
+    #[test]
+    fn license_templates_without_a_copyright_holder_are_unfilled() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "LICENSE-APACHE" => LICENSE_TEXTS["Apache-2.0"].to_string(),
+                _ => LICENSE_TEXTS["MIT"].to_string(),
+            })
+        });
+
+        let LicenseStatus::UnfilledTemplate(files) = validate_licenses(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            Some(&License::parse("MIT OR Apache-2.0")),
+            &license_files(&["LICENSE-APACHE", "LICENSE-MIT"]),
+        ) else {
+            panic!("expected an unfilled template");
+        };
+
+        assert_eq!(1, files.len());
+        assert_eq!("LICENSE-MIT", files[0].name);
+        assert_eq!(vec!["<year>", "<copyright holders>"], files[0].placeholders);
+    }
+
+    #[test]
+    fn license_templates_are_filled_by_a_copyright_holder_in_another_file() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_str().unwrap() {
+                "LICENSE-APACHE" => LICENSE_TEXTS["Apache-2.0"].to_string(),
+                _ => license_text("MIT"),
+            })
+        });
+
+        assert_eq!(
+            LicenseStatus::Valid,
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("MIT OR Apache-2.0")),
+                &license_files(&["LICENSE-APACHE", "LICENSE-MIT"]),
+            )
+        );
+    }
+
+    #[test]
+    fn each_license_concatenated_into_one_file_is_found() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|_: &PathBuf| {
+            Ok(format!(
+                "{}\n\n{}",
+                license_text("MIT"),
+                license_text("Apache-2.0")
+            ))
+        });
+
+        assert_eq!(
+            LicenseStatus::Valid,
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("MIT AND Apache-2.0")),
+                &license_files(&["LICENSE"]),
+            )
+        );
+        assert_eq!(
+            LicenseStatus::TooFew,
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("MIT AND Apache-2.0 AND BSD-3-Clause")),
+                &license_files(&["LICENSE"]),
+            )
+        );
+    }
+
+    #[test]
+    fn license_files_named_after_a_declared_license_are_paired_with_it_first() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_: &PathBuf| Ok(license_text("MIT")));
+
+        assert_eq!(
+            LicenseStatus::Additional(vec!["LICENSE".into()]),
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("MIT")),
+                &license_files(&["LICENSE", "LICENSE-MIT"]),
+            )
+        );
+    }
+
+    #[test]
+    fn license_files_named_after_another_declared_license_are_misnamed() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            Ok(if path.ends_with("LICENSE-MIT") {
+                license_text("Apache-2.0")
+            } else {
+                license_text("MIT")
+            })
+        });
+
+        let status = validate_licenses(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            Some(&License::parse("MIT OR Apache-2.0")),
+            &license_files(&["LICENSE-APACHE", "LICENSE-MIT"]),
+        );
+
+        assert_eq!(
+            LicenseStatus::Misnamed(vec!["LICENSE-APACHE".into(), "LICENSE-MIT".into()]),
+            status
+        );
+        assert_eq!(
+            Some("LICENSE-APACHE (detected MIT 1.00), LICENSE-MIT (detected Apache-2.0 1.00)"),
+            status.details().as_deref()
+        );
+    }
+
+    #[test]
+    fn license_names_hint_at_declared_licenses() {
+        let declared_ids =
+            License::parse("MIT OR Apache-2.0 OR BSD-2-Clause OR BSD-3-Clause").ids();
+        let hint = |name: &str| hinted_license_id(&license_files(&[name])[0], &declared_ids);
+
+        assert_eq!(Some("MIT"), hint("LICENSE-MIT"));
+        assert_eq!(Some("Apache-2.0"), hint("LICENSE-APACHE.txt"));
+        assert_eq!(Some("BSD-3-Clause"), hint("LICENSE-BSD-3-Clause"));
+        assert_eq!(None, hint("LICENSE-BSD"));
+        assert_eq!(None, hint("LICENSE"));
+    }
+
+    #[test]
+    fn license_files_named_by_id_outside_licenses_directory_are_matched_by_content() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|_: &PathBuf| Ok("reformatted beyond recognition".to_string()));
+
+        assert_eq!(
+            LicenseStatus::Mismatch(vec!["license/MIT.txt".into()]),
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("MIT")),
+                &license_files(&["license/MIT.txt"]),
+            )
+        );
+    }
+
+    #[test]
+    fn declared_exception_appended_to_license_is_valid() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set([Ok(format!(
+            "{}\n\n---- LLVM Exceptions to the Apache 2.0 License ----\n\n{}",
+            license_text("Apache-2.0"),
+            exception_text("LLVM-exception")
+        ))]);
+
+        assert_eq!(
+            LicenseStatus::Valid,
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("Apache-2.0 WITH LLVM-exception")),
+                &license_files(&["LICENSE"])
+            )
+        );
+    }
+
+    #[test]
+    fn declared_exception_in_separate_file_is_valid() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_string_lossy().as_ref() {
+                "LICENSE" => license_text("Apache-2.0"),
+                _ => exception_text("LLVM-exception"),
+            })
+        });
+
+        assert_eq!(
+            LicenseStatus::Valid,
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("Apache-2.0 WITH LLVM-exception")),
+                &license_files(&["LICENSE", "LLVM-EXCEPTION"])
+            )
+        );
+    }
+
+    #[test]
+    fn declared_exception_without_text_is_missing() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set([Ok(license_text("Apache-2.0"))]);
+
+        assert_eq!(
+            LicenseStatus::MissingException(vec!["LLVM-exception".to_string()]),
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("Apache-2.0 WITH LLVM-exception")),
+                &license_files(&["LICENSE"])
+            )
+        );
+    }
+
+    #[test]
+    fn exception_of_unsatisfied_alternative_is_not_required() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set([Ok(license_text("MIT"))]);
+
+        assert_eq!(
+            LicenseStatus::Satisfied("MIT".to_string()),
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("MIT OR Apache-2.0 WITH LLVM-exception")),
+                &license_files(&["LICENSE-MIT"])
+            )
+        );
+    }
+
+    #[test]
+    fn exception_file_not_declared_is_undeclared() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_string_lossy().as_ref() {
+                "LICENSE" => license_text("Apache-2.0"),
+                _ => exception_text("LLVM-exception"),
+            })
+        });
+
+        assert_eq!(
+            LicenseStatus::UndeclaredException(vec!["LLVM-EXCEPTION".to_string()]),
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("Apache-2.0")),
+                &license_files(&["LICENSE", "LLVM-EXCEPTION"])
+            )
+        );
+    }
+
+    #[test]
+    fn exception_appended_to_license_not_declared_is_undeclared() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set([Ok(format!(
+            "{}\n\n---- LLVM Exceptions to the Apache 2.0 License ----\n\n{}",
+            license_text("Apache-2.0"),
+            exception_text("LLVM-exception")
+        ))]);
+
+        assert_eq!(
+            LicenseStatus::UndeclaredException(vec!["LICENSE".to_string()]),
+            validate_licenses(
+                &file_io_spy,
+                &LicenseTexts::default(),
+                ConfidenceThreshold::default(),
+                Some(&License::parse("Apache-2.0")),
+                &license_files(&["LICENSE"])
+            )
+        );
+    }
+
+    #[test]
+    fn mismatch_reports_closest_match_score() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set([Ok(license_text("Apache-2.0"))]);
+
+        let LicenseStatus::Mismatch(files) = validate_licenses(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            Some(&License::parse("MIT")),
+            &license_files(&["LICENSE"]),
+        ) else {
+            panic!("expected a mismatch");
+        };
+
+        assert!(files[0].score.is_some_and(|score| score < 0.8));
+    }
+
+    #[test]
+    fn lower_confidence_threshold_accepts_partial_license() {
+        let mit = license_text("MIT");
+        let partial = mit[..mit.len() / 2].to_string();
+
+        for (threshold, valid) in [(0.8, false), (0.5, true)] {
+            let file_io_spy = FileIOSpy::default();
+            file_io_spy.read_file.returns.set([Ok(partial.clone())]);
+
+            assert_eq!(
+                valid,
+                validate_licenses(
+                    &file_io_spy,
+                    &LicenseTexts::default(),
+                    ConfidenceThreshold::try_from(threshold).unwrap(),
+                    Some(&License::parse("MIT")),
+                    &license_files(&["LICENSE"])
+                )
+                .is_valid()
+            );
+        }
+    }
+
+    #[test]
+    fn crate_confidence_threshold_overrides_global() {
+        let crate_configs = HashMap::from([(
+            "example".to_string(),
+            CrateConfig {
+                confidence_threshold: Some(ConfidenceThreshold::try_from(0.5).unwrap()),
+                ..CrateConfig::default()
+            },
+        )]);
+
+        assert_eq!(
+            ConfidenceThreshold::try_from(0.5).unwrap(),
+            ConfidenceThreshold::default().for_package(&Package::called("example"), &crate_configs)
+        );
+        assert_eq!(
+            ConfidenceThreshold::default(),
+            ConfidenceThreshold::default().for_package(&Package::called("other"), &crate_configs)
+        );
+    }
+
+    #[test]
+    fn confidence_threshold_must_be_between_zero_and_one() {
+        assert!("0.5".parse::<ConfidenceThreshold>().is_ok());
+        assert!("1.5".parse::<ConfidenceThreshold>().is_err());
+        assert!("high".parse::<ConfidenceThreshold>().is_err());
+    }
+
+    #[test]
+    fn additional_file_is_identified_against_all_license_texts() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_file.returns.set_fn(|path: &PathBuf| {
+            Ok(match path.to_string_lossy().as_ref() {
+                "LICENSE-MIT" => license_text("MIT"),
+                _ => license_text("Unlicense"),
+            })
+        });
+
+        let LicenseStatus::Additional(files) = validate_licenses(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            Some(&License::parse("MIT")),
+            &license_files(&["LICENSE-MIT", "COPYING"]),
+        ) else {
+            panic!("expected additional licenses");
+        };
+
+        assert_eq!(
+            Some("Unlicense"),
+            files[0]
+                .detected
+                .as_ref()
+                .map(|detected| detected.id.as_str())
+        );
+    }
+
+    #[test]
+    fn unconfident_identification_is_not_reported() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set([Ok("not a license".to_string())]);
+
+        let LicenseStatus::Mismatch(files) = validate_licenses(
+            &file_io_spy,
+            &LicenseTexts::default(),
+            ConfidenceThreshold::default(),
+            Some(&License::parse("MIT")),
+            &license_files(&["COPYING"]),
+        ) else {
+            panic!("expected a mismatch");
+        };
+
+        assert!(files[0].detected.is_none());
+    }
 }
@@ -4,6 +4,10 @@ pub fn warning(message: &str) -> String {
     format!("{}: {}", "warning".yellow().bold(), message)
 }
 
+pub fn note(message: &str) -> String {
+    format!("{}: {}", "note".cyan().bold(), message)
+}
+
 pub fn progress_bar(msg: &str) -> indicatif::ProgressBar {
     indicatif::ProgressBar::new(0)
         .with_style(
@@ -1,26 +1,37 @@
-use crate::cargo_metadata::{filtered_packages, try_get_packages};
-use crate::cargo_tree::crate_names;
-use crate::config::load_config;
+use crate::auditable::{packages_in_binary, try_get_binary_crates};
+use crate::cargo_metadata::{
+    MissingCrates, Package, filtered_packages, packages_from_metadata, read_metadata,
+    try_get_metadata, try_get_packages,
+};
+use crate::cargo_tree::{crate_names, crate_names_from_metadata};
+use crate::config::{Config, load_config};
 use crate::file_io::FileSystem;
 use crate::licenses::subcommand;
+use crate::licenses::validate::ConfidenceThreshold;
+use crate::vendor::try_get_vendored_packages;
 use clap::{Args, Parser, Subcommand};
 use serde::Deserialize;
 use std::path::PathBuf;
 use std::process::ExitCode;
 
+mod auditable;
 mod cargo_metadata;
 mod cargo_tree;
 mod config;
+mod crate_archive;
 mod file_io;
 mod licenses;
 mod log;
+mod vendor;
 
 fn main() -> anyhow::Result<ExitCode> {
     let CargoSubcommand::Licenses { args, command } = CargoSubcommand::parse();
 
-    let file_system = FileSystem {};
+    let file_system = FileSystem::default();
     let config = load_config(&file_system, args)?;
-    let filtered_packages = filtered_packages(try_get_packages()?, &crate_names(&config)?);
+    let (filtered_packages, missing) = packages(&file_system, &config)?;
+    // on stderr, so the JSON and TOML output stays valid
+    eprint!("{missing}");
 
     let exit_code = match command {
         LicensesSubcommand::Collect { path } => {
@@ -28,18 +39,63 @@ fn main() -> anyhow::Result<ExitCode> {
             ExitCode::SUCCESS
         }
         LicensesSubcommand::Summary(args) => {
-            subcommand::summary(filtered_packages, &args)?;
+            subcommand::summary(&file_system, &config, filtered_packages, &args)?;
             ExitCode::SUCCESS
         }
-        LicensesSubcommand::Check => subcommand::check(&file_system, &config, &filtered_packages)?,
+        LicensesSubcommand::Check(args) => {
+            let exit_code = subcommand::check(&file_system, &config, &filtered_packages, &args)?;
+            if missing.0.is_empty() {
+                exit_code
+            } else {
+                ExitCode::FAILURE
+            }
+        }
         LicensesSubcommand::Diff { path } => {
             subcommand::diff(&file_system, &config, &filtered_packages, &path)?
         }
+        LicensesSubcommand::Notices => {
+            subcommand::notices(&file_system, &config, &filtered_packages)?;
+            ExitCode::SUCCESS
+        }
+        LicensesSubcommand::Explain { name } => {
+            subcommand::explain(&file_system, &config, &filtered_packages, &name)?;
+            ExitCode::SUCCESS
+        }
     };
 
     Ok(exit_code)
 }
 
+fn packages(
+    file_system: &FileSystem,
+    config: &Config,
+) -> anyhow::Result<(Vec<Package>, MissingCrates)> {
+    if let Some(vendor) = &config.global.vendor {
+        let vendored = try_get_vendored_packages(file_system, vendor, &config.global.ignore)?;
+        return Ok((vendored.packages, vendored.missing));
+    }
+    if let Some(binary) = &config.global.binary {
+        let metadata = match &config.global.metadata {
+            Some(path) => read_metadata(path)?,
+            None => try_get_metadata()?,
+        };
+        return packages_in_binary(
+            metadata,
+            &try_get_binary_crates(binary, config.global.build)?,
+            &config.global.ignore,
+        );
+    }
+    let packages = match &config.global.metadata {
+        Some(path) => {
+            let metadata = read_metadata(path)?;
+            let crate_names = crate_names_from_metadata(&metadata, config)?;
+            filtered_packages(packages_from_metadata(metadata)?, &crate_names)
+        }
+        None => filtered_packages(try_get_packages()?, &crate_names(config)?),
+    };
+    Ok((packages, MissingCrates(Vec::new())))
+}
+
 #[derive(Parser)]
 #[command(bin_name = "cargo", disable_help_subcommand = true)]
 enum CargoSubcommand {
@@ -97,6 +153,72 @@ pub struct GlobalArgs {
     #[arg(short, long, value_name = "CRATE", global = true)]
     ignore: Vec<String>,
 
+    /// Prefer specified license when a crate offers alternatives [default: all alternatives]
+    #[arg(short, long, value_name = "LICENSE", global = true)]
+    prefer: Vec<String>,
+
+    /// Path to a directory of additional license texts [default: bundled SPDX texts]
+    #[arg(long, value_name = "PATH", global = true)]
+    #[serde(rename = "license-texts")]
+    license_texts: Option<PathBuf>,
+
+    /// Scan other top-level text files for license texts [default: license file names only]
+    #[arg(long, global = true)]
+    #[serde(rename = "scan-content")]
+    scan_content: bool,
+
+    /// Search directories with a license name, such as LICENSES/, for license files [default: crate root only]
+    #[arg(long, global = true)]
+    #[serde(rename = "scan-license-dirs")]
+    scan_license_dirs: bool,
+
+    /// Scan source files for SPDX-License-Identifier headers and REUSE files [default: not scanned]
+    #[arg(long, global = true)]
+    #[serde(rename = "scan-headers")]
+    scan_headers: bool,
+
+    /// Report license files of third-party code vendored in crate subdirectories [default: not reported]
+    #[arg(long, global = true)]
+    #[serde(rename = "scan-embedded")]
+    scan_embedded: bool,
+
+    /// Collect the license files of vendored third-party code as <crate>-embedded-<path> [default: not collected]
+    #[arg(long, global = true)]
+    #[serde(rename = "collect-embedded")]
+    collect_embedded: bool,
+
+    /// Use the license files of other cached versions of registry crates without any [default: not used]
+    #[arg(long, global = true)]
+    #[serde(rename = "sibling-versions")]
+    sibling_versions: bool,
+
+    /// Minimum similarity between a file and a license text to match [default: 0.8]
+    #[arg(long, value_name = "THRESHOLD", global = true)]
+    #[serde(rename = "confidence-threshold")]
+    confidence_threshold: Option<ConfidenceThreshold>,
+
+    /// Path to a `cargo vendor` directory to read the crates of Cargo.lock from, without calling cargo [default: cargo metadata]
+    #[arg(
+        long,
+        value_name = "PATH",
+        global = true,
+        conflicts_with_all = ["dev", "build", "depth", "all_features", "no_default_features", "feature"]
+    )]
+    vendor: Option<PathBuf>,
+
+    /// Path to the output of `cargo metadata --format-version 1` to use instead of calling cargo, or - for stdin [default: cargo metadata]
+    #[arg(
+        long,
+        value_name = "PATH",
+        global = true,
+        conflicts_with_all = ["vendor", "all_features", "no_default_features", "feature"]
+    )]
+    metadata: Option<PathBuf>,
+
+    /// Path to an ELF binary built with cargo-auditable, to check only the crates compiled into it [default: all dependencies]
+    #[arg(long, value_name = "PATH", global = true, conflicts_with = "vendor")]
+    binary: Option<PathBuf>,
+
     /// Path to configuration file
     #[arg(short, long, value_name = "PATH", global = true)]
     #[serde(skip)]
@@ -114,22 +236,43 @@ enum LicensesSubcommand {
     /// Provides a summary of all licenses
     Summary(SummaryArgs),
     /// Checks all licenses for inconsistencies
-    Check,
+    Check(CheckArgs),
     /// Diff between the current licenses folder and the licenses that would be collected
     Diff {
         /// The current licenses folder path
         #[arg(short, long, default_value = "licenses")]
         path: PathBuf,
     },
+    /// Provides a notice containing the licenses of all crates
+    Notices,
+    /// Explains how the licenses of a single crate were checked
+    Explain {
+        /// The crate to explain
+        #[arg(value_name = "CRATE")]
+        name: String,
+    },
 }
 
 #[derive(Args)]
 #[group(required = false, multiple = false)]
+struct CheckArgs {
+    /// Display the check results as JSON
+    #[arg(long)]
+    json: bool,
+    /// Display the check results as TOML
+    #[arg(long)]
+    toml: bool,
+}
+
+#[derive(Args)]
 struct SummaryArgs {
     /// Display the summary as JSON
-    #[arg(long)]
+    #[arg(long, conflicts_with = "toml")]
     json: bool,
     /// Display the summary as TOML
     #[arg(long)]
     toml: bool,
+    /// Include the copyright holders of each crate, from its license files or authors
+    #[arg(long)]
+    copyright: bool,
 }
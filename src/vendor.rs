@@ -0,0 +1,244 @@
+use crate::cargo_metadata::{MissingCrates, Package};
+use crate::file_io::FileIO;
+use anyhow::Context;
+use cargo_metadata::camino::Utf8PathBuf;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+#[derive(Deserialize)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: ManifestPackage,
+}
+
+#[derive(Deserialize)]
+struct ManifestPackage {
+    version: String,
+    license: Option<String>,
+    repository: Option<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+}
+
+pub struct Vendored {
+    pub packages: Vec<Package>,
+    pub missing: MissingCrates,
+}
+
+pub fn try_get_vendored_packages(
+    file_io: &impl FileIO,
+    vendor: &Path,
+    ignored_crates: &[String],
+) -> anyhow::Result<Vendored> {
+    let current_dir = std::env::current_dir().context("failed to get the current directory")?;
+    let lockfile = find_lockfile(file_io, &current_dir)
+        .context("failed to find Cargo.lock in the current directory or its parents")?;
+    let lockfile = std::fs::read_to_string(&lockfile)
+        .with_context(|| format!("failed to read {}", lockfile.display()))?;
+    vendored_packages(file_io, &lockfile, vendor, ignored_crates)
+}
+
+fn find_lockfile(file_io: &impl FileIO, directory: &Path) -> Option<PathBuf> {
+    directory
+        .ancestors()
+        .map_while(|directory| file_io.read_dir(directory).ok())
+        .flatten()
+        .find(|dir_entry| dir_entry.is_file && dir_entry.name == "Cargo.lock")
+        .map(|dir_entry| dir_entry.path)
+}
+
+fn vendored_packages(
+    file_io: &impl FileIO,
+    lockfile: &str,
+    vendor: &Path,
+    ignored_crates: &[String],
+) -> anyhow::Result<Vendored> {
+    let lockfile: Lockfile = toml::from_str(lockfile).context("failed to parse Cargo.lock")?;
+    let mut packages = Vec::new();
+    let mut missing = Vec::new();
+    for locked in lockfile.package.into_iter().filter(|locked| {
+        locked.source.is_some() && !ignored_crates.contains(&locked.name.replace('-', "_"))
+    }) {
+        match vendored_package(file_io, vendor, &locked)? {
+            Some(package) => packages.push(package),
+            None => missing.push(format!("{} {}", locked.name, locked.version)),
+        }
+    }
+    Ok(Vendored {
+        packages,
+        missing: MissingCrates(missing),
+    })
+}
+
+// the newest version of a crate is vendored as `<crate>`, any others as `<crate>-<version>`
+fn vendored_package(
+    file_io: &impl FileIO,
+    vendor: &Path,
+    locked: &LockedPackage,
+) -> anyhow::Result<Option<Package>> {
+    for directory in [
+        vendor.join(format!("{}-{}", locked.name, locked.version)),
+        vendor.join(&locked.name),
+    ] {
+        let Ok(contents) = file_io.read_file(&directory.join("Cargo.toml")) else {
+            continue;
+        };
+        let manifest: Manifest = toml::from_str(&contents)
+            .with_context(|| format!("failed to parse the vendored manifest of {}", locked.name))?;
+        if manifest.package.version != locked.version {
+            continue;
+        }
+        return Ok(Some(Package {
+            normalised_name: locked.name.replace('-', "_"),
+            path: Utf8PathBuf::try_from(directory)
+                .context("vendor directory path is not valid UTF-8")?,
+            url: manifest.package.repository,
+            license: manifest.package.license,
+            authors: manifest.package.authors,
+            source: locked.source.clone(),
+        }));
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::{DirEntry, FileIOSpy};
+    use std::ffi::OsString;
+
+    const LOCKFILE: &str = r#"
+version = 4
+
+[[package]]
+name = "workspace-member"
+version = "0.1.0"
+dependencies = ["example-crate"]
+
+[[package]]
+name = "example-crate"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "example-crate"
+version = "0.9.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "absent"
+version = "2.0.0"
+source = "git+https://example.com/absent#0123456789"
+"#;
+
+    fn manifest(version: &str) -> String {
+        format!(
+            "[package]\nname = \"example-crate\"\nversion = \"{version}\"\nlicense = \"MIT\"\nauthors = [\"Example\"]\n"
+        )
+    }
+
+    #[test]
+    fn locked_packages_are_read_from_the_vendor_directory() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy
+            .read_file
+            .returns
+            .set_fn(|path: &PathBuf| match path.to_str().unwrap() {
+                "vendor/example-crate/Cargo.toml" => Ok(manifest("1.0.0")),
+                "vendor/example-crate-0.9.0/Cargo.toml" => Ok(manifest("0.9.0")),
+                _ => Err(anyhow::anyhow!("deliberate test error")),
+            });
+
+        let vendored = vendored_packages(&file_io_spy, LOCKFILE, Path::new("vendor"), &[]).unwrap();
+
+        assert_eq!(
+            vec![
+                Utf8PathBuf::from("vendor/example-crate"),
+                Utf8PathBuf::from("vendor/example-crate-0.9.0")
+            ],
+            vendored
+                .packages
+                .iter()
+                .map(|package| package.path.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Package {
+                path: Utf8PathBuf::from("vendor/example-crate"),
+                license: Some("MIT".to_string()),
+                authors: vec!["Example".to_string()],
+                source: Some("registry+https://github.com/rust-lang/crates.io-index".to_string()),
+                ..Package::called("example_crate")
+            },
+            vendored.packages[0]
+        );
+        assert_eq!(
+            MissingCrates(vec!["absent 2.0.0".to_string()]),
+            vendored.missing
+        );
+        let display = strip_ansi_escapes::strip_str(vendored.missing.to_string());
+        assert!(display.starts_with("warning: missing"));
+        assert!(display.contains("absent 2.0.0"));
+    }
+
+    #[test]
+    fn ignored_crates_are_neither_read_nor_missing() {
+        let vendored = vendored_packages(
+            &FileIOSpy::default(),
+            LOCKFILE,
+            Path::new("vendor"),
+            &["example_crate".to_string(), "absent".to_string()],
+        )
+        .unwrap();
+
+        assert!(vendored.packages.is_empty());
+        assert!(vendored.missing.0.is_empty());
+    }
+
+    #[test]
+    fn lockfile_is_found_in_the_nearest_parent_directory() {
+        let file_io_spy = FileIOSpy::default();
+        file_io_spy.read_dir.returns.set_fn(|path: &PathBuf| {
+            let lockfile = |directory: &str| DirEntry {
+                name: OsString::from("Cargo.lock"),
+                path: Path::new(directory).join("Cargo.lock"),
+                is_file: true,
+            };
+            Ok(match path.to_str().unwrap() {
+                "/workspace" => vec![lockfile("/workspace")],
+                "/" => vec![lockfile("/")],
+                _ => Vec::new(),
+            })
+        });
+
+        assert_eq!(
+            Some(PathBuf::from("/workspace/Cargo.lock")),
+            find_lockfile(&file_io_spy, Path::new("/workspace/crates/member"))
+        );
+    }
+
+    #[test]
+    fn invalid_lockfile_is_an_error() {
+        assert!(
+            vendored_packages(
+                &FileIOSpy::default(),
+                "[[package]]",
+                Path::new("vendor"),
+                &[]
+            )
+            .is_err()
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
+use std::io::Write;
 use std::path::Path;
-use std::process::{Command, Output};
+use std::process::{Command, Output, Stdio};
 
 fn call_licenses_command(args: &[&str]) -> Output {
     assert!(
@@ -51,6 +52,34 @@ fn summary_depth_1() {
     );
 }
 
+#[test]
+fn summary_depth_1_from_metadata_on_stdin() {
+    let metadata = Command::new("cargo")
+        .args(["metadata", "--format-version", "1"])
+        .output()
+        .unwrap();
+    assert!(metadata.status.success());
+
+    let mut child = Command::new("target/release/cargo-licenses")
+        .args(["licenses", "summary", "--depth", "1", "--metadata", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(&metadata.stdout)
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        include_str!("stdout/summary"),
+        String::from_utf8(output.stdout).unwrap()
+    );
+}
+
 #[test]
 fn collect_depth_1() {
     let temp_dir = tempfile::TempDir::new().unwrap();
@@ -82,6 +111,27 @@ fn check_warns_about_unused_config() {
     assert!(stdout.contains("strsim - 'skip' for NONEXISTENT is not required"));
 }
 
+#[test]
+fn check_json_reports_unused_config() {
+    let output = call_licenses_command(&[
+        "check",
+        "--config",
+        "tests/data/unused_config.toml",
+        "--json",
+    ]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(parsed["crates"].is_array());
+    assert!(
+        parsed["unused"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!({"name": "fake_crate", "reason": "crate not found"}))
+    );
+}
+
 #[test]
 fn check_depth_1_succeeds() {
     let output = call_licenses_command(&["check", "--depth", "1"]);
@@ -149,6 +199,29 @@ fn collect_into_temp_dir_creates_expected_files() {
     assert!(files.iter().all(|f| f.contains('-')));
 }
 
+#[test]
+fn notices_depth_1_reports_preferred_license() {
+    let output = call_licenses_command(&["notices", "--depth", "1", "--prefer", "MIT"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("anyhow - MIT (chosen from MIT OR Apache-2.0)"));
+}
+
+#[test]
+fn explain_reports_declared_license_and_status() {
+    let output = call_licenses_command(&["explain", "anyhow", "--depth", "1"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("declared: MIT OR Apache-2.0"));
+    assert!(stdout.contains("status: valid"));
+}
+
+#[test]
+fn explain_fails_for_crate_not_in_dependencies() {
+    let output = call_licenses_command(&["explain", "not_a_dependency", "--depth", "1"]);
+    assert!(!output.status.success());
+}
+
 #[test]
 fn invalid_subcommand_fails() {
     let output = Command::new("target/release/cargo-licenses")